@@ -72,14 +72,14 @@ impl Compiler {
     }
 
     fn emit_move_ptr(&self, diff: i64){
-        let mut indices = [self.ctx.SInt32(diff as u64)];
+        let mut indices = [self.ctx.SInt32(diff)];
         let gep = self.builder.build_inbounds_gep(self.builder.build_load(self.ptr), &mut indices);
         self.builder.build_store(gep, self.ptr);
     }
 
     fn emit_add(&self, diff: i64){
         let tmp = self.builder.build_load(self.ptr);
-        let add = self.builder.build_add(self.builder.build_load(tmp), self.ctx.SInt8(diff as u64));
+        let add = self.builder.build_add(self.builder.build_load(tmp), self.ctx.SInt8(diff));
         self.builder.build_store(add, tmp);
     }
 
@@ -0,0 +1,49 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use self::llvm_sys::LLVMLinkage;
+use value::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alias {
+    llvm_alias: LLVMValueRef
+}
+
+impl Alias {
+    pub fn from_ptr(alias_ptr: LLVMValueRef) -> Alias {
+        Alias {
+            llvm_alias: alias_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMValueRef {
+        self.llvm_alias
+    }
+
+    #[inline]
+    pub fn get_name(&self) -> String {
+        Value::from_ptr(self.llvm_alias).get_name()
+    }
+
+    // the aliasee is operand 0 of the alias
+    #[inline]
+    pub fn get_aliasee(&self) -> LLVMValueRef {
+        unsafe { LLVMGetOperand(self.llvm_alias, 0) }
+    }
+
+    #[inline]
+    pub fn set_aliasee(&self, aliasee: LLVMValueRef){
+        unsafe { LLVMSetOperand(self.llvm_alias, 0, aliasee) }
+    }
+
+    #[inline]
+    pub fn get_linkage(&self) -> LLVMLinkage {
+        unsafe { LLVMGetLinkage(self.llvm_alias) }
+    }
+
+    #[inline]
+    pub fn set_linkage(&self, linkage: LLVMLinkage){
+        unsafe { LLVMSetLinkage(self.llvm_alias, linkage) }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,246 @@
+use basic_block::BasicBlock;
+use function::Function;
+use std::collections::HashMap;
+
+// control flow graph of a function built from the successors of each terminator
+#[derive(Debug)]
+pub struct Cfg {
+    blocks: Vec<BasicBlock>,
+    indices: HashMap<BasicBlock, usize>,
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+    rpo: Vec<usize>,
+}
+
+impl Cfg {
+    pub fn new(function: &Function) -> Cfg {
+        let blocks = function.get_basic_blocks();
+        let indices: HashMap<BasicBlock, usize> = blocks.iter().enumerate().map(|(i, block)| (*block, i)).collect();
+        let mut successors = vec![Vec::new(); blocks.len()];
+        let mut predecessors = vec![Vec::new(); blocks.len()];
+        for (i, block) in blocks.iter().enumerate() {
+            for successor in block.get_successors() {
+                let j = indices[&successor];
+                if !successors[i].contains(&j) {
+                    successors[i].push(j);
+                    predecessors[j].push(i);
+                }
+            }
+        }
+
+        let mut cfg = Cfg {
+            blocks: blocks,
+            indices: indices,
+            successors: successors,
+            predecessors: predecessors,
+            rpo: Vec::new(),
+        };
+        cfg.rpo = cfg.compute_rpo();
+        cfg
+    }
+
+    // iterative depth first search from the entry block
+    fn compute_rpo(&self) -> Vec<usize> {
+        let mut order = Vec::new();
+        if self.blocks.is_empty() {
+            return order;
+        }
+        let mut visited = vec![false; self.blocks.len()];
+        let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+        visited[0] = true;
+        while let Some(&(node, next)) = stack.last() {
+            if next < self.successors[node].len() {
+                let successor = self.successors[node][next];
+                stack.last_mut().unwrap().1 += 1;
+                if !visited[successor] {
+                    visited[successor] = true;
+                    stack.push((successor, 0));
+                }
+            }else{
+                order.push(node);
+                stack.pop();
+            }
+        }
+        order.reverse();
+        order
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    pub fn entry(&self) -> Option<BasicBlock> {
+        self.blocks.first().cloned()
+    }
+
+    pub fn successors(&self, block: BasicBlock) -> Vec<BasicBlock> {
+        self.indices.get(&block).map_or(Vec::new(), |&i| self.successors[i].iter().map(|&j| self.blocks[j]).collect())
+    }
+
+    pub fn predecessors(&self, block: BasicBlock) -> Vec<BasicBlock> {
+        self.indices.get(&block).map_or(Vec::new(), |&i| self.predecessors[i].iter().map(|&j| self.blocks[j]).collect())
+    }
+
+    // reachable blocks only
+    pub fn reverse_post_order(&self) -> Vec<BasicBlock> {
+        self.rpo.iter().map(|&i| self.blocks[i]).collect()
+    }
+
+    pub fn is_reachable(&self, block: BasicBlock) -> bool {
+        self.indices.get(&block).map_or(false, |i| self.rpo.contains(i))
+    }
+}
+
+// Cooper, Harvey and Kennedy, "A Simple, Fast Dominance Algorithm"
+#[derive(Debug)]
+pub struct DominatorTree {
+    blocks: Vec<BasicBlock>,
+    indices: HashMap<BasicBlock, usize>,
+    idoms: Vec<Option<usize>>,
+}
+
+impl DominatorTree {
+    pub fn new(cfg: &Cfg) -> DominatorTree {
+        let count = cfg.blocks.len();
+        let mut idoms: Vec<Option<usize>> = vec![None; count];
+        let mut order = vec![usize::max_value(); count];
+        for (position, &i) in cfg.rpo.iter().enumerate() {
+            order[i] = position;
+        }
+
+        if let Some(&entry) = cfg.rpo.first() {
+            idoms[entry] = Some(entry);
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for &block in cfg.rpo.iter().skip(1) {
+                    let mut new_idom: Option<usize> = None;
+                    for &pred in cfg.predecessors[block].iter() {
+                        if idoms[pred].is_none() {
+                            continue;
+                        }
+                        new_idom = match new_idom {
+                            None => Some(pred),
+                            Some(current) => Some(DominatorTree::intersect(&idoms, &order, pred, current))
+                        };
+                    }
+                    if new_idom.is_some() && idoms[block] != new_idom {
+                        idoms[block] = new_idom;
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        DominatorTree {
+            blocks: cfg.blocks.clone(),
+            indices: cfg.indices.clone(),
+            idoms: idoms,
+        }
+    }
+
+    fn intersect(idoms: &[Option<usize>], order: &[usize], a: usize, b: usize) -> usize {
+        let (mut a, mut b) = (a, b);
+        while a != b {
+            while order[a] > order[b] {
+                a = idoms[a].unwrap();
+            }
+            while order[b] > order[a] {
+                b = idoms[b].unwrap();
+            }
+        }
+        a
+    }
+
+    // None for the entry block and unreachable blocks
+    pub fn immediate_dominator(&self, block: BasicBlock) -> Option<BasicBlock> {
+        let i = *self.indices.get(&block)?;
+        match self.idoms[i] {
+            Some(idom) if idom != i => Some(self.blocks[idom]),
+            _ => None
+        }
+    }
+
+    pub fn dominates(&self, a: BasicBlock, b: BasicBlock) -> bool {
+        let (a, mut b) = match (self.indices.get(&a), self.indices.get(&b)) {
+            (Some(&a), Some(&b)) => (a, b),
+            _ => return false
+        };
+        if self.idoms[b].is_none() {
+            return false;
+        }
+        loop {
+            if a == b {
+                return true;
+            }
+            match self.idoms[b] {
+                Some(idom) if idom != b => b = idom,
+                _ => return false
+            }
+        }
+    }
+
+    pub fn children(&self, block: BasicBlock) -> Vec<BasicBlock> {
+        match self.indices.get(&block) {
+            Some(&i) => (0..self.blocks.len()).filter(|&j| j != i && self.idoms[j] == Some(i)).map(|j| self.blocks[j]).collect(),
+            None => Vec::new()
+        }
+    }
+}
+
+// natural loop formed by the back edges into a header
+#[derive(Debug, Clone)]
+pub struct Loop {
+    header: BasicBlock,
+    latches: Vec<BasicBlock>,
+    blocks: Vec<BasicBlock>,
+}
+
+impl Loop {
+    // loops ordered by their headers in reverse post order, outer loops first
+    pub fn find_loops(cfg: &Cfg, dominators: &DominatorTree) -> Vec<Loop> {
+        let mut loops: Vec<Loop> = Vec::new();
+        for &header in cfg.rpo.iter() {
+            let latches: Vec<usize> = cfg.predecessors[header].iter().cloned()
+                .filter(|&pred| dominators.dominates(cfg.blocks[header], cfg.blocks[pred]))
+                .collect();
+            if latches.is_empty() {
+                continue;
+            }
+
+            let mut in_loop = vec![false; cfg.blocks.len()];
+            in_loop[header] = true;
+            let mut worklist = latches.clone();
+            while let Some(block) = worklist.pop() {
+                if in_loop[block] {
+                    continue;
+                }
+                in_loop[block] = true;
+                worklist.extend(cfg.predecessors[block].iter().cloned().filter(|&pred| cfg.is_reachable(cfg.blocks[pred])));
+            }
+
+            loops.push(Loop {
+                header: cfg.blocks[header],
+                latches: latches.iter().map(|&i| cfg.blocks[i]).collect(),
+                blocks: cfg.rpo.iter().cloned().filter(|&i| in_loop[i]).map(|i| cfg.blocks[i]).collect(),
+            });
+        }
+        loops
+    }
+
+    pub fn header(&self) -> BasicBlock {
+        self.header
+    }
+
+    pub fn latches(&self) -> &[BasicBlock] {
+        &self.latches
+    }
+
+    pub fn blocks(&self) -> &[BasicBlock] {
+        &self.blocks
+    }
+
+    pub fn contains(&self, block: BasicBlock) -> bool {
+        self.blocks.contains(&block)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use std::ffi::CStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BasicBlock {
+    llvm_basic_block: LLVMBasicBlockRef
+}
+
+impl BasicBlock {
+    pub fn from_ptr(block_ptr: LLVMBasicBlockRef) -> BasicBlock {
+        BasicBlock {
+            llvm_basic_block: block_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMBasicBlockRef {
+        self.llvm_basic_block
+    }
+
+    pub fn get_name(&self) -> String {
+        let name = unsafe { LLVMGetBasicBlockName(self.llvm_basic_block) };
+        if name.is_null() {
+            String::new()
+        }else{
+            unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned()
+        }
+    }
+
+    #[inline]
+    pub fn get_parent(&self) -> LLVMValueRef {
+        unsafe { LLVMGetBasicBlockParent(self.llvm_basic_block) }
+    }
+
+    pub fn get_instructions(&self) -> Vec<LLVMValueRef> {
+        let mut instructions = Vec::new();
+        let mut inst = unsafe { LLVMGetFirstInstruction(self.llvm_basic_block) };
+        while !inst.is_null() {
+            instructions.push(inst);
+            inst = unsafe { LLVMGetNextInstruction(inst) };
+        }
+        instructions
+    }
+
+    #[inline]
+    pub fn get_terminator(&self) -> Option<LLVMValueRef> {
+        let terminator = unsafe { LLVMGetBasicBlockTerminator(self.llvm_basic_block) };
+        if terminator.is_null() {
+            None
+        }else{
+            Some(terminator)
+        }
+    }
+
+    pub fn get_successors(&self) -> Vec<BasicBlock> {
+        match self.get_terminator() {
+            Some(terminator) => {
+                let count = unsafe { LLVMGetNumSuccessors(terminator) };
+                (0..count).map(|i| BasicBlock::from_ptr(unsafe { LLVMGetSuccessor(terminator, i) })).collect()
+            },
+            None => Vec::new()
+        }
+    }
+}
\ No newline at end of file
@@ -8,6 +8,13 @@ use self::llvm_sys::LLVMIntPredicate::*;
 use self::llvm_sys::LLVMRealPredicate::*;
 use phi::Phi;
 use cstring_manager::CStringManager;
+use error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsmDialect {
+    ATT,
+    Intel,
+}
 
 #[derive(Debug)]
 pub struct Builder {
@@ -299,6 +306,11 @@ impl Builder {
         unsafe { LLVMBuildRetVoid(self.llvm_builder) }
     }
 
+    #[inline]
+    pub fn build_unreachable(&self) -> LLVMValueRef {
+        unsafe { LLVMBuildUnreachable(self.llvm_builder) }
+    }
+
     #[inline]
     pub fn build_and(&self, lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
         self.build_add_with_name(lhs, rhs, "")
@@ -695,6 +707,24 @@ impl Builder {
         unsafe { LLVMBuildFCmp(self.llvm_builder, LLVMRealULE, lhs, rhs, val_name_ptr) }
     }
 
+    // inline assembler value of function_type, called with build_call
+    pub fn build_inline_asm(&self, function_type: LLVMTypeRef, asm: &str, constraints: &str, side_effects: bool, align_stack: bool, dialect: AsmDialect) -> Result<LLVMValueRef, Error> {
+        // LLVMConstInlineAsm has no dialect parameter, so switch the assembler syntax around Intel code.
+        // operands would still be substituted in AT&T syntax, so Intel code can only use fixed registers
+        let asm = match dialect {
+            AsmDialect::ATT => asm.to_string(),
+            AsmDialect::Intel => {
+                if has_asm_operands(asm) {
+                    return Err(Error::Parse(format!("operands can not be used with AsmDialect::Intel in '{}'", asm)));
+                }
+                format!(".intel_syntax noprefix\n{}\n.att_syntax prefix", asm)
+            }
+        };
+        let asm_ptr = CStringManager::new_cstring_as_ptr(&asm);
+        let constraints_ptr = CStringManager::new_cstring_as_ptr(constraints);
+        Ok(unsafe { LLVMConstInlineAsm(function_type, asm_ptr, constraints_ptr, side_effects as LLVMBool, align_stack as LLVMBool) })
+    }
+
     #[inline]
     pub fn build_global_string_ptr(&self, string: &str) -> LLVMValueRef {
         self.build_global_string_ptr_with_name(string, "")
@@ -785,6 +815,247 @@ impl Builder {
     pub fn build_phi_with_name(&self, typ: LLVMTypeRef, name: &str) -> Phi {
         Phi::new(self.llvm_builder, typ, name)
     }
+
+    #[inline]
+    pub fn get_insert_block(&self) -> LLVMBasicBlockRef {
+        unsafe { LLVMGetInsertBlock(self.llvm_builder) }
+    }
+
+    //
+    // control flow helpers
+    //
+    pub fn build_if<T, E>(&self, cond: LLVMValueRef, then_body: T, else_body: E) -> Option<LLVMValueRef>
+        where T: FnOnce(&Builder) -> Option<LLVMValueRef>,
+              E: FnOnce(&Builder) -> Option<LLVMValueRef>
+    {
+        let then_block = self.append_block("then");
+        let else_block = self.append_block("else");
+        let merge_block = self.append_block("ifcont");
+        self.build_cond_br(cond, then_block, else_block);
+
+        let mut incomings = Vec::new();
+
+        self.position_at_end(then_block);
+        let then_value = then_body(self);
+        self.finish_branch(then_value, merge_block, &mut incomings);
+
+        self.position_at_end(else_block);
+        let else_value = else_body(self);
+        self.finish_branch(else_value, merge_block, &mut incomings);
+
+        self.merge_branches(merge_block, incomings)
+    }
+
+    pub fn build_while<C, B>(&self, cond: C, body: B)
+        where C: FnOnce(&Builder) -> LLVMValueRef,
+              B: FnOnce(&Builder)
+    {
+        let cond_block = self.append_block("while.cond");
+        let body_block = self.append_block("while.body");
+        let end_block = self.append_block("while.end");
+        self.build_br(cond_block);
+
+        self.position_at_end(cond_block);
+        let cond_value = cond(self);
+        self.build_cond_br(cond_value, body_block, end_block);
+
+        self.position_at_end(body_block);
+        body(self);
+        if !self.is_terminated() {
+            self.build_br(cond_block);
+        }
+
+        self.position_at_end(end_block);
+    }
+
+    // for (i = start; i < end; i += step) body(i)   (signed comparison)
+    pub fn build_for<B>(&self, start: LLVMValueRef, end: LLVMValueRef, step: LLVMValueRef, body: B)
+        where B: FnOnce(&Builder, LLVMValueRef)
+    {
+        let preheader_block = self.get_insert_block();
+        let cond_block = self.append_block("for.cond");
+        let body_block = self.append_block("for.body");
+        let inc_block = self.append_block("for.inc");
+        let end_block = self.append_block("for.end");
+        self.build_br(cond_block);
+
+        self.position_at_end(cond_block);
+        let index = self.build_phi_with_name(unsafe { LLVMTypeOf(start) }, "i");
+        index.add_incoming(start, preheader_block);
+        let cond_value = self.build_icmp_slt(index.as_ref(), end);
+        self.build_cond_br(cond_value, body_block, end_block);
+
+        self.position_at_end(body_block);
+        body(self, index.as_ref());
+        if !self.is_terminated() {
+            self.build_br(inc_block);
+        }
+
+        self.position_at_end(inc_block);
+        let next = self.build_add_with_name(index.as_ref(), step, "i.next");
+        self.build_br(cond_block);
+        index.add_incoming(next, inc_block);
+
+        self.position_at_end(end_block);
+    }
+
+    pub fn build_switch_with<'a, D>(&self, value: LLVMValueRef, cases: Vec<(LLVMValueRef, Box<dyn Fn(&Builder) -> Option<LLVMValueRef> + 'a>)>, default: D) -> Option<LLVMValueRef>
+        where D: FnOnce(&Builder) -> Option<LLVMValueRef>
+    {
+        let default_block = self.append_block("switch.default");
+        let case_blocks: Vec<LLVMBasicBlockRef> = cases.iter().map(|_| self.append_block("switch.case")).collect();
+        let merge_block = self.append_block("switch.end");
+
+        let mut case_pairs = Vec::with_capacity(cases.len());
+        for (case, block) in cases.iter().zip(case_blocks.iter()) {
+            case_pairs.push((case.0, *block));
+        }
+        self.build_switch(value, default_block, &case_pairs);
+
+        let mut incomings = Vec::new();
+        for (case, block) in cases.iter().zip(case_blocks.iter()) {
+            self.position_at_end(*block);
+            let case_value = (case.1)(self);
+            self.finish_branch(case_value, merge_block, &mut incomings);
+        }
+
+        self.position_at_end(default_block);
+        let default_value = default(self);
+        self.finish_branch(default_value, merge_block, &mut incomings);
+
+        self.merge_branches(merge_block, incomings)
+    }
+
+    //
+    // garbage collector intrinsics
+    //
+    // root is an i8** alloca in the entry block, metadata a constant i8* or null
+    pub fn build_gcroot(&self, root: LLVMValueRef, metadata: LLVMValueRef) -> LLVMValueRef {
+        let context = self.get_context();
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(context), 0) };
+        let gcroot = self.get_or_declare_intrinsic("llvm.gcroot", unsafe { LLVMVoidTypeInContext(context) }, &mut [unsafe { LLVMPointerType(i8_ptr, 0) }, i8_ptr]);
+        let mut args = [root, metadata];
+        self.build_call(gcroot, &mut args)
+    }
+
+    pub fn build_gcread(&self, object: LLVMValueRef, field: LLVMValueRef) -> LLVMValueRef {
+        let context = self.get_context();
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(context), 0) };
+        let gcread = self.get_or_declare_intrinsic("llvm.gcread", i8_ptr, &mut [i8_ptr, unsafe { LLVMPointerType(i8_ptr, 0) }]);
+        let mut args = [object, field];
+        self.build_call(gcread, &mut args)
+    }
+
+    pub fn build_gcwrite(&self, value: LLVMValueRef, object: LLVMValueRef, field: LLVMValueRef) -> LLVMValueRef {
+        let context = self.get_context();
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(context), 0) };
+        let gcwrite = self.get_or_declare_intrinsic("llvm.gcwrite", unsafe { LLVMVoidTypeInContext(context) }, &mut [i8_ptr, i8_ptr, unsafe { LLVMPointerType(i8_ptr, 0) }]);
+        let mut args = [value, object, field];
+        self.build_call(gcwrite, &mut args)
+    }
+
+    //
+    // variable arguments
+    //
+    // va_list points to the target's va_list storage, e.g. an alloca of [1 x {i32, i32, i8*, i8*}] on x86-64
+    pub fn build_va_start(&self, va_list: LLVMValueRef) -> LLVMValueRef {
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(self.get_context()), 0) };
+        let va_start = self.get_or_declare_intrinsic("llvm.va_start", unsafe { LLVMVoidTypeInContext(self.get_context()) }, &mut [i8_ptr]);
+        let mut args = [self.build_bitcast(va_list, i8_ptr)];
+        self.build_call(va_start, &mut args)
+    }
+
+    pub fn build_va_end(&self, va_list: LLVMValueRef) -> LLVMValueRef {
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(self.get_context()), 0) };
+        let va_end = self.get_or_declare_intrinsic("llvm.va_end", unsafe { LLVMVoidTypeInContext(self.get_context()) }, &mut [i8_ptr]);
+        let mut args = [self.build_bitcast(va_list, i8_ptr)];
+        self.build_call(va_end, &mut args)
+    }
+
+    pub fn build_va_copy(&self, dest: LLVMValueRef, src: LLVMValueRef) -> LLVMValueRef {
+        let i8_ptr = unsafe { LLVMPointerType(LLVMInt8TypeInContext(self.get_context()), 0) };
+        let va_copy = self.get_or_declare_intrinsic("llvm.va_copy", unsafe { LLVMVoidTypeInContext(self.get_context()) }, &mut [i8_ptr, i8_ptr]);
+        let mut args = [self.build_bitcast(dest, i8_ptr), self.build_bitcast(src, i8_ptr)];
+        self.build_call(va_copy, &mut args)
+    }
+
+    #[inline]
+    pub fn build_va_arg(&self, va_list: LLVMValueRef, typ: LLVMTypeRef) -> LLVMValueRef {
+        self.build_va_arg_with_name(va_list, typ, "")
+    }
+
+    #[inline]
+    pub fn build_va_arg_with_name(&self, va_list: LLVMValueRef, typ: LLVMTypeRef, name: &str) -> LLVMValueRef {
+        let val_name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMBuildVAArg(self.llvm_builder, va_list, typ, val_name_ptr) }
+    }
+
+    fn get_context(&self) -> LLVMContextRef {
+        unsafe { LLVMGetTypeContext(LLVMTypeOf(LLVMGetBasicBlockParent(self.get_insert_block()))) }
+    }
+
+    // declares the intrinsic in the module of the current function
+    fn get_or_declare_intrinsic(&self, name: &str, return_type: LLVMTypeRef, param_types: &mut [LLVMTypeRef]) -> LLVMValueRef {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe {
+            let module = LLVMGetGlobalParent(LLVMGetBasicBlockParent(self.get_insert_block()));
+            let function = LLVMGetNamedFunction(module, name_ptr);
+            if function.is_null() {
+                let function_type = LLVMFunctionType(return_type, param_types.as_mut_ptr(), param_types.len() as u32, 0);
+                LLVMAddFunction(module, name_ptr, function_type)
+            }else{
+                function
+            }
+        }
+    }
+
+    fn append_block(&self, name: &str) -> LLVMBasicBlockRef {
+        let label_name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe {
+            let function = LLVMGetBasicBlockParent(self.get_insert_block());
+            LLVMAppendBasicBlockInContext(self.get_context(), function, label_name_ptr)
+        }
+    }
+
+    fn is_terminated(&self) -> bool {
+        unsafe { !LLVMGetBasicBlockTerminator(self.get_insert_block()).is_null() }
+    }
+
+    fn finish_branch(&self, value: Option<LLVMValueRef>, merge_block: LLVMBasicBlockRef, incomings: &mut Vec<(Option<LLVMValueRef>, LLVMBasicBlockRef)>){
+        // branches which already end with their own terminator (ret, br, ...) do not reach the merge block
+        if !self.is_terminated() {
+            let block = self.get_insert_block();
+            self.build_br(merge_block);
+            incomings.push((value, block));
+        }
+    }
+
+    fn merge_branches(&self, merge_block: LLVMBasicBlockRef, incomings: Vec<(Option<LLVMValueRef>, LLVMBasicBlockRef)>) -> Option<LLVMValueRef> {
+        // when no branch falls through, the merge block has no predecessors and
+        // is left for the caller to terminate (e.g. with build_unreachable)
+        self.position_at_end(merge_block);
+        if incomings.is_empty() {
+            return None;
+        }
+
+        let values_count = incomings.iter().filter(|&&(value, _)| value.is_some()).count();
+        if values_count == 0 {
+            return None;
+        }
+        if values_count != incomings.len() {
+            panic!("some branches yield a value and others do not.");
+        }
+        if incomings.len() == 1 {
+            return incomings[0].0;
+        }
+
+        let typ = unsafe { LLVMTypeOf(incomings[0].0.unwrap()) };
+        let phi = self.build_phi(typ);
+        for (value, block) in incomings {
+            phi.add_incoming(value.unwrap(), block);
+        }
+        Some(phi.as_ref())
+    }
 }
 
 impl Drop for Builder {
@@ -792,4 +1063,19 @@ impl Drop for Builder {
     fn drop(&mut self) {
         unsafe { LLVMDisposeBuilder(self.llvm_builder) }
     }
+}
+
+// '$$' is a literal '$', '$N' and '${N:modifier}' refer to operands
+fn has_asm_operands(asm: &str) -> bool {
+    let mut chars = asm.chars();
+    while let Some(c) = chars.next() {
+        if c == '$' {
+            match chars.next() {
+                Some('$') => {},
+                Some(next) if next == '{' || next.is_digit(10) => return true,
+                _ => {}
+            }
+        }
+    }
+    false
 }
\ No newline at end of file
@@ -0,0 +1,392 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use self::llvm_sys::LLVMIntPredicate::*;
+use self::llvm_sys::LLVMRealPredicate::*;
+use std::os::raw::c_uint;
+
+// constant expressions mirroring Builder, usable as global initializers
+pub struct ConstExpr;
+
+impl ConstExpr {
+    //
+    // address computations
+    //
+    #[inline]
+    pub fn gep(ptr: LLVMValueRef, indices: &mut [LLVMValueRef]) -> LLVMValueRef {
+        unsafe { LLVMConstGEP(ptr, indices.as_mut_ptr(), indices.len() as c_uint) }
+    }
+
+    #[inline]
+    pub fn inbounds_gep(ptr: LLVMValueRef, indices: &mut [LLVMValueRef]) -> LLVMValueRef {
+        unsafe { LLVMConstInBoundsGEP(ptr, indices.as_mut_ptr(), indices.len() as c_uint) }
+    }
+
+    pub fn struct_gep(ptr: LLVMValueRef, index: u32) -> LLVMValueRef {
+        unsafe {
+            let int32_type = LLVMInt32TypeInContext(LLVMGetTypeContext(LLVMTypeOf(ptr)));
+            let mut indices = [LLVMConstInt(int32_type, 0, 0), LLVMConstInt(int32_type, index as u64, 0)];
+            LLVMConstInBoundsGEP(ptr, indices.as_mut_ptr(), indices.len() as c_uint)
+        }
+    }
+
+    //
+    // casts
+    //
+    #[inline]
+    pub fn int_to_ptr(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstIntToPtr(value, to_type) }
+    }
+
+    #[inline]
+    pub fn ptr_to_int(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstPtrToInt(value, to_type) }
+    }
+
+    #[inline]
+    pub fn bitcast(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstBitCast(value, to_type) }
+    }
+
+    #[inline]
+    pub fn pointer_cast(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstPointerCast(value, to_type) }
+    }
+
+    #[inline]
+    pub fn zext(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstZExt(value, to_type) }
+    }
+
+    #[inline]
+    pub fn sext(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstSExt(value, to_type) }
+    }
+
+    #[inline]
+    pub fn trunc(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstTrunc(value, to_type) }
+    }
+
+    #[inline]
+    pub fn trunc_or_bitcast(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstTruncOrBitCast(value, to_type) }
+    }
+
+    #[inline]
+    pub fn fp_trunc(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstFPTrunc(value, to_type) }
+    }
+
+    #[inline]
+    pub fn fp_ext(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstFPExt(value, to_type) }
+    }
+
+    #[inline]
+    pub fn si_to_fp(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstSIToFP(value, to_type) }
+    }
+
+    #[inline]
+    pub fn ui_to_fp(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstUIToFP(value, to_type) }
+    }
+
+    #[inline]
+    pub fn fp_to_si(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstFPToSI(value, to_type) }
+    }
+
+    #[inline]
+    pub fn fp_to_ui(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstFPToUI(value, to_type) }
+    }
+
+    #[inline]
+    pub fn addrspace_cast(value: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
+        unsafe { LLVMConstAddrSpaceCast(value, to_type) }
+    }
+
+    #[inline]
+    pub fn int_cast(value: LLVMValueRef, to_type: LLVMTypeRef, signed: bool) -> LLVMValueRef {
+        unsafe { LLVMConstIntCast(value, to_type, signed as LLVMBool) }
+    }
+
+    //
+    // arithmetic
+    //
+    #[inline]
+    pub fn add(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstAdd(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn nsw_add(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstNSWAdd(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn nuw_add(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstNUWAdd(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn sub(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstSub(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn mul(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstMul(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn sdiv(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstSDiv(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn exact_sdiv(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstExactSDiv(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn udiv(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstUDiv(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn srem(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstSRem(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn urem(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstURem(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fadd(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFAdd(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fsub(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFSub(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fmul(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFMul(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fdiv(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFDiv(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn frem(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFRem(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn and(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstAnd(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn or(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstOr(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn xor(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstXor(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn shl(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstShl(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn ashr(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstAShr(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn lshr(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstLShr(lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn neg(value: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstNeg(value) }
+    }
+
+    #[inline]
+    pub fn fneg(value: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFNeg(value) }
+    }
+
+    #[inline]
+    pub fn not(value: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstNot(value) }
+    }
+
+    //
+    // comparisons
+    //
+    #[inline]
+    pub fn icmp_eq(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntEQ, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_ne(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntNE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_ugt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntUGT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_uge(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntUGE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_ult(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntULT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_ule(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntULE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_sgt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntSGT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_sge(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntSGE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_slt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntSLT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn icmp_sle(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstICmp(LLVMIntSLE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_oeq(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealOEQ, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_one(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealONE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ogt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealOGT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_oge(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealOGE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_olt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealOLT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ole(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealOLE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ord(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealORD, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_uno(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealUNO, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ueq(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealUEQ, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_une(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealUNE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ugt(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealUGT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_uge(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealUGE, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ult(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealULT, lhs, rhs) }
+    }
+
+    #[inline]
+    pub fn fcmp_ule(lhs: LLVMValueRef, rhs: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstFCmp(LLVMRealULE, lhs, rhs) }
+    }
+
+    //
+    // select and aggregates
+    //
+    #[inline]
+    pub fn select(cond: LLVMValueRef, then_val: LLVMValueRef, else_val: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstSelect(cond, then_val, else_val) }
+    }
+
+    #[inline]
+    pub fn extract_value(agg_val: LLVMValueRef, index: u32) -> LLVMValueRef {
+        let mut indices = [index as c_uint];
+        unsafe { LLVMConstExtractValue(agg_val, indices.as_mut_ptr(), indices.len() as c_uint) }
+    }
+
+    #[inline]
+    pub fn insert_value(agg_val: LLVMValueRef, elt_val: LLVMValueRef, index: u32) -> LLVMValueRef {
+        let mut indices = [index as c_uint];
+        unsafe { LLVMConstInsertValue(agg_val, elt_val, indices.as_mut_ptr(), indices.len() as c_uint) }
+    }
+
+    #[inline]
+    pub fn extract_element(vector: LLVMValueRef, index: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstExtractElement(vector, index) }
+    }
+
+    #[inline]
+    pub fn insert_element(vector: LLVMValueRef, element: LLVMValueRef, index: LLVMValueRef) -> LLVMValueRef {
+        unsafe { LLVMConstInsertElement(vector, element, index) }
+    }
+}
\ No newline at end of file
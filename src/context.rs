@@ -4,11 +4,14 @@ extern crate llvm_sys;
 
 use self::llvm_sys::core::*;
 use self::llvm_sys::prelude::*;
-use std::os::raw::c_uint;
+use std::os::raw::{c_char, c_uint};
 use LLVM::Type;
 use builder::Builder;
 use module::Module;
 use struct_type::Struct;
+use metadata::Metadata;
+use type_info::{TypeInfo, TypeKind};
+use error::Error;
 
 #[derive(Debug)]
 pub struct Context {
@@ -50,6 +53,24 @@ impl Context {
         Module::new_in_context(name, self.as_ref())
     }
 
+    #[inline]
+    pub fn metadata_kind_id(&self, name: &str) -> u32 {
+        unsafe { LLVMGetMDKindIDInContext(self.llvm_context, name.as_ptr() as *const c_char, name.len() as c_uint) }
+    }
+
+    //
+    // Metadata
+    //
+    #[inline]
+    pub fn MDString(&self, string: &str) -> Metadata {
+        Metadata::new_string(self.llvm_context, string)
+    }
+
+    #[inline]
+    pub fn MDNode(&self, values: &mut [LLVMValueRef]) -> Metadata {
+        Metadata::new_node(self.llvm_context, values)
+    }
+
     //
     // get Type
     //
@@ -141,6 +162,21 @@ impl Context {
         Type::PointerType(typ, 0)
     }
 
+    #[inline]
+    pub fn ArrayType(&self, elem_type: LLVMTypeRef, count: u32) -> LLVMTypeRef {
+        unsafe { LLVMArrayType(elem_type, count) }
+    }
+
+    #[inline]
+    pub fn VectorType(&self, elem_type: LLVMTypeRef, count: u32) -> LLVMTypeRef {
+        unsafe { LLVMVectorType(elem_type, count) }
+    }
+
+    #[inline]
+    pub fn FunctionType(&self, return_type: LLVMTypeRef, param_types: &mut [LLVMTypeRef], is_var_arg: bool) -> LLVMTypeRef {
+        unsafe { LLVMFunctionType(return_type, param_types.as_mut_ptr(), param_types.len() as u32, if is_var_arg {1}else{0}) }
+    }
+
     //
     // define Constant util
     //
@@ -154,65 +190,88 @@ impl Context {
         unsafe { LLVMConstPointerNull(typ) }
     }
 
+    #[inline]
+    pub fn ConstArray(&self, elem_type: LLVMTypeRef, values: &mut [LLVMValueRef]) -> LLVMValueRef {
+        unsafe { LLVMConstArray(elem_type, values.as_mut_ptr(), values.len() as u32) }
+    }
+
+    #[inline]
+    pub fn ConstString(&self, bytes: &[u8], null_terminated: bool) -> LLVMValueRef {
+        unsafe { LLVMConstStringInContext(self.llvm_context, bytes.as_ptr() as *const c_char, bytes.len() as c_uint, if null_terminated {0}else{1}) }
+    }
+
     #[inline]
     pub fn Bitcast(&self, constant: LLVMValueRef, to_type: LLVMTypeRef) -> LLVMValueRef {
         unsafe { LLVMConstBitCast(constant, to_type) }
     }
     #[inline]
-    pub fn SInt(&self, num_bits: c_uint, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMIntTypeInContext(self.llvm_context, num_bits), val, 1) }
+    pub fn SInt(&self, num_bits: c_uint, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMIntTypeInContext(self.llvm_context, num_bits), val as u64, 1) }
     }
     #[inline]
     pub fn UInt(&self, num_bits: c_uint, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMIntTypeInContext(self.llvm_context, num_bits), val, 0) }
     }
     #[inline]
-    pub fn SInt1(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt1TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt1(&self, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMInt1TypeInContext(self.llvm_context), val as u64, 1) }
     }
     #[inline]
     pub fn UInt1(&self, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMInt1TypeInContext(self.llvm_context), val, 0) }
     }
     #[inline]
-    pub fn SInt8(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt8TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt8(&self, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMInt8TypeInContext(self.llvm_context), val as u64, 1) }
     }
     #[inline]
     pub fn UInt8(&self, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMInt8TypeInContext(self.llvm_context), val, 0) }
     }
     #[inline]
-    pub fn SInt16(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt16TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt16(&self, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMInt16TypeInContext(self.llvm_context), val as u64, 1) }
     }
     #[inline]
     pub fn UInt16(&self, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMInt16TypeInContext(self.llvm_context), val, 0) }
     }
     #[inline]
-    pub fn SInt32(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt32TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt32(&self, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMInt32TypeInContext(self.llvm_context), val as u64, 1) }
     }
     #[inline]
     pub fn UInt32(&self, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMInt32TypeInContext(self.llvm_context), val, 0) }
     }
     #[inline]
-    pub fn SInt64(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt64TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt64(&self, val: i64) -> LLVMValueRef {
+        unsafe { LLVMConstInt(LLVMInt64TypeInContext(self.llvm_context), val as u64, 1) }
     }
     #[inline]
     pub fn UInt64(&self, val: u64) -> LLVMValueRef {
         unsafe { LLVMConstInt(LLVMInt64TypeInContext(self.llvm_context), val, 0) }
     }
     #[inline]
-    pub fn SInt128(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt128TypeInContext(self.llvm_context), val, 1) }
+    pub fn SInt128(&self, val: i128) -> LLVMValueRef {
+        self.UInt128(val as u128)
     }
     #[inline]
-    pub fn UInt128(&self, val: u64) -> LLVMValueRef {
-        unsafe { LLVMConstInt(LLVMInt128TypeInContext(self.llvm_context), val, 0) }
+    pub fn UInt128(&self, val: u128) -> LLVMValueRef {
+        let words = [val as u64, (val >> 64) as u64];
+        unsafe { LLVMConstIntOfArbitraryPrecision(LLVMInt128TypeInContext(self.llvm_context), 2, words.as_ptr()) }
+    }
+    // words are least significant first
+    #[inline]
+    pub fn IntOfArbitraryPrecision(&self, num_bits: c_uint, words: &[u64]) -> Result<LLVMValueRef, Error> {
+        check_int_width(num_bits)?;
+        Ok(unsafe { LLVMConstIntOfArbitraryPrecision(LLVMIntTypeInContext(self.llvm_context, num_bits), words.len() as c_uint, words.as_ptr()) })
+    }
+    // e.g. ("-42", 10) or ("ff", 16)
+    #[inline]
+    pub fn IntOfString(&self, num_bits: c_uint, text: &str, radix: u8) -> Result<LLVMValueRef, Error> {
+        check_int_string(num_bits, text, radix)?;
+        Ok(unsafe { LLVMConstIntOfStringAndSize(LLVMIntTypeInContext(self.llvm_context, num_bits), text.as_ptr() as *const c_char, text.len() as c_uint, radix) })
     }
 
     #[inline]
@@ -239,4 +298,92 @@ impl Context {
     pub fn PPCFP128(&self, val: f64) -> LLVMValueRef {
         unsafe { LLVMConstReal(LLVMPPCFP128TypeInContext(self.llvm_context), val) }
     }
+    // exact for types wider than double, e.g. FP128 or X86FP80
+    #[inline]
+    pub fn RealOfString(&self, typ: LLVMTypeRef, text: &str) -> Result<LLVMValueRef, Error> {
+        check_real_string(typ, text)?;
+        Ok(unsafe { LLVMConstRealOfStringAndSize(typ, text.as_ptr() as *const c_char, text.len() as c_uint) })
+    }
+}
+
+// LLVM asserts on text it can not parse, so IntOfString and RealOfString check it first
+const MAX_INT_BITS: c_uint = (1 << 24) - 1;
+
+pub(crate) fn check_int_width(num_bits: c_uint) -> Result<(), Error> {
+    if num_bits == 0 || num_bits > MAX_INT_BITS {
+        Err(Error::Parse(format!("invalid integer width {}", num_bits)))
+    }else{
+        Ok(())
+    }
+}
+
+pub(crate) fn check_int_string(num_bits: c_uint, text: &str, radix: u8) -> Result<(), Error> {
+    check_int_width(num_bits)?;
+    match radix {
+        2 | 8 | 10 | 16 | 36 => {},
+        _ => return Err(Error::Parse(format!("invalid radix {}, expected 2, 8, 10, 16 or 36", radix)))
+    }
+    let digits = if text.starts_with('-') || text.starts_with('+') { &text[1..] } else { text };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix as u32)) {
+        return Err(Error::Parse(format!("'{}' is not a base {} integer", text, radix)));
+    }
+    // same bounds as the assertions in APInt::fromString
+    let length = digits.len() as c_uint;
+    let fits = match radix {
+        2  => length <= num_bits,
+        8  => (length - 1) * 3 <= num_bits,
+        16 => (length - 1) * 4 <= num_bits,
+        10 => (length - 1) * 64 / 22 <= num_bits,
+        _  => true
+    };
+    if fits {
+        Ok(())
+    }else{
+        Err(Error::Parse(format!("'{}' does not fit in i{}", text, num_bits)))
+    }
+}
+
+pub(crate) fn check_real_string(typ: LLVMTypeRef, text: &str) -> Result<(), Error> {
+    match TypeInfo::from_ptr(typ).kind() {
+        TypeKind::Half | TypeKind::Float | TypeKind::Double |
+        TypeKind::X86FP80 | TypeKind::FP128 | TypeKind::PPCFP128 => {},
+        kind => return Err(Error::Parse(format!("{:?} is not a floating point type", kind)))
+    }
+    if is_real_literal(text) {
+        Ok(())
+    }else{
+        Err(Error::Parse(format!("'{}' is not a floating point number", text)))
+    }
+}
+
+// accepts what APFloat::convertFromString accepts: the special values, decimal
+// numbers with an optional exponent and hexadecimal numbers with a binary exponent
+fn is_real_literal(text: &str) -> bool {
+    match text {
+        "inf" | "-inf" | "INFINITY" | "-INFINITY" | "nan" | "NaN" => return true,
+        _ => {}
+    }
+    let body = if text.starts_with('-') || text.starts_with('+') { &text[1..] } else { text };
+    if body.starts_with("0x") || body.starts_with("0X") {
+        match body[2..].find(|c| c == 'p' || c == 'P') {
+            Some(index) => is_significand(&body[2..2 + index], 16) && is_exponent(&body[3 + index..]),
+            None => false
+        }
+    }else{
+        match body.find(|c| c == 'e' || c == 'E') {
+            Some(index) => is_significand(&body[..index], 10) && is_exponent(&body[index + 1..]),
+            None => is_significand(body, 10)
+        }
+    }
+}
+
+fn is_significand(text: &str, radix: u32) -> bool {
+    text.chars().all(|c| c == '.' || c.is_digit(radix))
+        && text.chars().filter(|&c| c == '.').count() <= 1
+        && text.chars().any(|c| c.is_digit(radix))
+}
+
+fn is_exponent(text: &str) -> bool {
+    let digits = if text.starts_with('-') || text.starts_with('+') { &text[1..] } else { text };
+    !digits.is_empty() && digits.chars().all(|c| c.is_digit(10))
 }
\ No newline at end of file
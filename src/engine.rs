@@ -3,10 +3,19 @@ extern crate libc;
 
 use self::llvm_sys::prelude::*;
 use self::llvm_sys::execution_engine::*;
-use std::ffi::CString;
 use std::os::raw::{c_char, c_ulonglong, c_uint};
 use self::libc::c_void;
+use LLVM;
 use LLVM::Type;
+use llvm_string::LLVMString;
+use error::Error;
+use cstring_manager::CStringManager;
+use module::Module;
+use function::Function;
+use host_function::HostFunction;
+use mcjit::{self, McJitOptions};
+use std::mem;
+use std::ffi::CString;
 
 #[derive(Debug)]
 pub struct Engine {
@@ -14,7 +23,7 @@ pub struct Engine {
 }
 
 impl Engine {
-    pub fn create_interpreter(module: LLVMModuleRef) -> Result<Engine, String> {
+    pub fn create_interpreter(module: LLVMModuleRef) -> Result<Engine, Error> {
         let mut error: *mut c_char = 0 as *mut c_char;
         let mut engine: LLVMExecutionEngineRef = 0 as LLVMExecutionEngineRef;
         let result = unsafe {
@@ -25,8 +34,7 @@ impl Engine {
         };        
 
         if result == 1 { // error
-            let err_msg = unsafe { CString::from_raw(error).into_string().unwrap() };
-            Err(err_msg)
+            Err(Error::EngineCreation(LLVMString::from_ptr(error).to_string()))
 
         }else{           // ok
             Ok(Engine {
@@ -35,19 +43,27 @@ impl Engine {
         }
     }
 
-    pub fn create_jit_engine(module: LLVMModuleRef) -> Result<Engine, String> {
+    #[inline]
+    pub fn create_jit_engine(module: LLVMModuleRef) -> Result<Engine, Error> {
+        Engine::create_jit_engine_with_options(module, McJitOptions::new())
+    }
+
+    pub fn create_jit_engine_with_options(module: LLVMModuleRef, options: McJitOptions) -> Result<Engine, Error> {
         let mut error: *mut c_char = 0 as *mut c_char;
         let mut engine: LLVMExecutionEngineRef = 0 as LLVMExecutionEngineRef;
+        let (mut options, memory_manager) = options.into_llvm_options();
         let result = unsafe {
             let buf: *mut *mut c_char = &mut error;
             let engine_ref: *mut LLVMExecutionEngineRef = &mut engine;
             LLVMLinkInMCJIT();
-            LLVMCreateMCJITCompilerForModule(engine_ref, module, 0 as *mut LLVMMCJITCompilerOptions, 0, buf)
+            if let Some(memory_manager) = memory_manager {
+                options.MCJMM = mcjit::create_llvm_memory_manager(memory_manager);
+            }
+            LLVMCreateMCJITCompilerForModule(engine_ref, module, &mut options, mem::size_of::<LLVMMCJITCompilerOptions>(), buf)
         };        
 
         if result == 1 { // error
-            let err_msg = unsafe { CString::from_raw(error).into_string().unwrap() };
-            Err(err_msg)
+            Err(Error::EngineCreation(LLVMString::from_ptr(error).to_string()))
 
         }else{           // ok
             Ok(Engine {
@@ -64,6 +80,115 @@ impl Engine {
         let func_result = unsafe { LLVMRunFunction(self.llvm_execute_engine, function, args.len() as u32, args.as_mut_ptr()) };
         FuncallResult::new(func_result)
     }
+
+    // the engine takes ownership of the module until it is removed again
+    pub fn add_module(&self, module: Module) -> EngineModule {
+        let llvm_module = module.into_ref();
+        unsafe { LLVMAddModule(self.llvm_execute_engine, llvm_module) }
+        EngineModule {
+            llvm_execute_engine: self.llvm_execute_engine,
+            llvm_module: llvm_module
+        }
+    }
+
+    pub fn remove_module(&self, module: EngineModule) -> Result<Module, Error> {
+        if module.llvm_execute_engine != self.llvm_execute_engine {
+            return Err(Error::Jit("module was added to a different engine".to_string()));
+        }
+        let mut out_module: LLVMModuleRef = 0 as LLVMModuleRef;
+        let mut error: *mut c_char = 0 as *mut c_char;
+        let result = unsafe { LLVMRemoveModule(self.llvm_execute_engine, module.llvm_module, &mut out_module, &mut error) };
+        if result == 1 { // error
+            Err(Error::Jit(LLVMString::from_ptr(error).to_string()))
+        }else{ // success
+            Ok(Module::from_ptr(out_module))
+        }
+    }
+
+    // searches all modules of the engine
+    pub fn find_function(&self, name: &str) -> Option<Function> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let mut function: LLVMValueRef = 0 as LLVMValueRef;
+        let result = unsafe { LLVMFindFunction(self.llvm_execute_engine, name_ptr, &mut function) };
+        if result == 0 && !function.is_null() {
+            Some(Function::from_ptr(function))
+        }else{
+            None
+        }
+    }
+
+    pub fn get_global_value_address(&self, name: &str) -> Option<u64> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let address = unsafe { LLVMGetGlobalValueAddress(self.llvm_execute_engine, name_ptr) };
+        if address == 0 { None } else { Some(address) }
+    }
+
+    // compiles the function if needed
+    pub fn get_function_address(&self, name: &str) -> Option<u64> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let address = unsafe { LLVMGetFunctionAddress(self.llvm_execute_engine, name_ptr) };
+        if address == 0 { None } else { Some(address) }
+    }
+
+    // falls back to host symbols and permanently loaded libraries
+    pub fn get_symbol_address(&self, name: &str) -> Option<u64> {
+        self.get_global_value_address(name)
+            .or_else(|| LLVM::search_for_address_of_symbol(name).map(|address| address as u64))
+    }
+
+    #[inline]
+    pub fn add_global_mapping(&self, global: LLVMValueRef, address: *mut c_void){
+        unsafe { LLVMAddGlobalMapping(self.llvm_execute_engine, global, address) }
+    }
+
+    // calls to the declared function run the host function instead
+    pub fn add_host_function<F: HostFunction>(&self, function: &Function, host_function: F) -> Result<(), Error> {
+        if function.params_count() != F::params_count() {
+            return Err(Error::Jit(format!("'{}' takes {} parameters, but the host function takes {}",
+                                          function.get_name(), function.params_count(), F::params_count())));
+        }
+        self.add_global_mapping(function.as_ref(), host_function.address());
+        Ok(())
+    }
+
+    #[inline]
+    pub fn run_static_constructors(&self){
+        unsafe { LLVMRunStaticConstructors(self.llvm_execute_engine) }
+    }
+
+    #[inline]
+    pub fn run_static_destructors(&self){
+        unsafe { LLVMRunStaticDestructors(self.llvm_execute_engine) }
+    }
+
+    // calls function as 'int main(int argc, char **argv, char **envp)'
+    pub fn run_function_as_main(&self, function: LLVMValueRef, args: &[&str], env: &[&str]) -> Result<i32, Error> {
+        let args = to_cstrings(args)?;
+        let env = to_cstrings(env)?;
+        let argv: Vec<*const c_char> = args.iter().map(|arg| arg.as_ptr()).collect();
+        let mut envp: Vec<*const c_char> = env.iter().map(|var| var.as_ptr()).collect();
+        envp.push(0 as *const c_char);
+        Ok(unsafe { LLVMRunFunctionAsMain(self.llvm_execute_engine, function, argv.len() as c_uint, argv.as_ptr(), envp.as_ptr()) as i32 })
+    }
+}
+
+fn to_cstrings(strings: &[&str]) -> Result<Vec<CString>, Error> {
+    strings.iter().map(|string| {
+        CString::new(*string).map_err(|_| Error::Jit(format!("'{}' contains a nul byte", string.escape_default())))
+    }).collect()
+}
+
+// a module added to an engine, which can be taken back with Engine::remove_module
+#[derive(Debug, PartialEq, Eq)]
+pub struct EngineModule {
+    llvm_execute_engine: LLVMExecutionEngineRef,
+    llvm_module: LLVMModuleRef
+}
+
+impl EngineModule {
+    pub fn as_ref(&self) -> LLVMModuleRef {
+        self.llvm_module
+    }
 }
 
 pub struct FuncallResult {
@@ -0,0 +1,42 @@
+use std::error;
+use std::fmt;
+use std::io;
+use verify::VerifyError;
+
+#[derive(Debug)]
+pub enum Error {
+    Verify(VerifyError),
+    Io(io::Error),
+    Parse(String),
+    EngineCreation(String),
+    Target(String),
+    Jit(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Verify(ref err)         => write!(f, "verification failed: {}", err),
+            Error::Io(ref msg)             => write!(f, "io error: {}", msg),
+            Error::Parse(ref msg)          => write!(f, "parse error: {}", msg),
+            Error::EngineCreation(ref msg) => write!(f, "could not create execution engine: {}", msg),
+            Error::Target(ref msg)         => write!(f, "target error: {}", msg),
+            Error::Jit(ref msg)            => write!(f, "jit error: {}", msg),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            Error::Io(ref err) => Some(err),
+            _                  => None
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        Error::Io(err)
+    }
+}
\ No newline at end of file
@@ -4,7 +4,16 @@ extern crate llvm_sys;
 
 use self::llvm_sys::core::*;
 use self::llvm_sys::prelude::*;
+use self::llvm_sys::analysis::{LLVMVerifyFunction, LLVMVerifyModule, LLVMVerifierFailureAction};
 use cstring_manager::CStringManager;
+use type_info::TypeInfo;
+use value::Value;
+use basic_block::BasicBlock;
+use verify::VerifyError;
+use llvm_string::LLVMString;
+use std::os::raw::c_char;
+use std::collections::HashMap;
+use std::ffi::CStr;
 
 #[derive(Debug)]
 pub struct Function {
@@ -25,10 +34,19 @@ impl Function {
     }
 
     pub fn from_ptr(func_ptr: LLVMValueRef) -> Function {
+        if func_ptr.is_null() {
+            return Function {
+                llvm_function: func_ptr,
+                llvm_module: 0 as LLVMModuleRef,
+                function_type: 0 as LLVMTypeRef,
+            };
+        }
+        // a function value is a pointer to its function type
+        let (module, function_type) = unsafe { (LLVMGetGlobalParent(func_ptr), LLVMGetElementType(LLVMTypeOf(func_ptr))) };
         Function {
             llvm_function: func_ptr,
-            llvm_module: 0 as LLVMModuleRef,
-            function_type: 0 as LLVMTypeRef,
+            llvm_module: module,
+            function_type: function_type,
         }
     }
 
@@ -67,12 +85,151 @@ impl Function {
     }
 
     #[inline]
-    pub fn get_param_types(&self) -> LLVMTypeRef {
-        let mut types : LLVMTypeRef = 0 as LLVMTypeRef;
-        let ptr: *mut LLVMTypeRef = &mut types;
+    pub fn get_param_types(&self) -> Vec<LLVMTypeRef> {
+        TypeInfo::from_ptr(self.function_type).param_types()
+    }
+
+    #[inline]
+    pub fn is_var_arg(&self) -> bool {
+        TypeInfo::from_ptr(self.function_type).is_var_arg()
+    }
+
+    // "shadow-stack", "statepoint-example" or a custom strategy
+    #[inline]
+    pub fn set_gc(&self, name: &str){
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMSetGC(self.llvm_function, name_ptr) }
+    }
+
+    pub fn gc(&self) -> Option<String> {
+        let name = unsafe { LLVMGetGC(self.llvm_function) };
+        if name.is_null() {
+            None
+        }else{
+            Some(unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned())
+        }
+    }
+
+    #[inline]
+    pub fn get_name(&self) -> String {
+        Value::from_ptr(self.llvm_function).get_name()
+    }
+
+    #[inline]
+    pub fn is_declaration(&self) -> bool {
+        unsafe { LLVMIsDeclaration(self.llvm_function) != 0 }
+    }
+
+    pub fn get_basic_blocks(&self) -> Vec<BasicBlock> {
+        let mut blocks = Vec::new();
+        let mut block = unsafe { LLVMGetFirstBasicBlock(self.llvm_function) };
+        while !block.is_null() {
+            blocks.push(BasicBlock::from_ptr(block));
+            block = unsafe { LLVMGetNextBasicBlock(block) };
+        }
+        blocks
+    }
+
+    #[inline]
+    pub fn get_entry_block(&self) -> Option<BasicBlock> {
+        if self.is_declaration() {
+            None
+        }else{
+            Some(BasicBlock::from_ptr(unsafe { LLVMGetEntryBasicBlock(self.llvm_function) }))
+        }
+    }
+
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let result = unsafe { LLVMVerifyFunction(self.llvm_function, LLVMVerifierFailureAction::LLVMReturnStatusAction) };
+        if result == 0 { // success
+            return Ok(());
+        }
+
+        // LLVMVerifyFunction gives no message, so collect the diagnostics from the whole module
+        let module = unsafe { LLVMGetGlobalParent(self.llvm_function) };
+        let mut error: *mut c_char = 0 as *mut c_char;
         unsafe {
-            LLVMGetParamTypes(self.function_type, ptr);
+            let buf: *mut *mut c_char = &mut error;
+            LLVMVerifyModule(module, LLVMVerifierFailureAction::LLVMReturnStatusAction, buf);
+        }
+        let message = LLVMString::from_ptr(error);
+        Err(VerifyError::from_message(module, &message.to_string()).for_function(&self.get_name()))
+    }
+
+    // render the control flow graph in Graphviz DOT format
+    pub fn to_dot(&self) -> String {
+        let name = self.get_name();
+        let blocks = self.get_basic_blocks();
+        let ids: HashMap<BasicBlock, usize> = blocks.iter().enumerate().map(|(i, block)| (*block, i)).collect();
+
+        let mut dot = format!("digraph \"CFG for '{}' function\" {{\n", escape_dot(&name));
+        dot.push_str(&format!("    label=\"CFG for '{}' function\";\n", escape_dot(&name)));
+        dot.push_str("    node [shape=record];\n");
+        for (i, block) in blocks.iter().enumerate() {
+            let mut label = format!("{}:\\l", escape_dot(&block.get_name()));
+            for inst in block.get_instructions() {
+                label.push_str(&escape_dot(Value::from_ptr(inst).print_to_string().trim()));
+                label.push_str("\\l");
+            }
+            dot.push_str(&format!("    block{} [label=\"{{{}}}\"];\n", i, label));
+        }
+        for (i, block) in blocks.iter().enumerate() {
+            let labels = match block.get_terminator() {
+                Some(terminator) => edge_labels(terminator),
+                None => Vec::new()
+            };
+            for (j, successor) in block.get_successors().iter().enumerate() {
+                let target = ids[successor];
+                match labels.get(j) {
+                    Some(label) => dot.push_str(&format!("    block{} -> block{} [label=\"{}\"];\n", i, target, escape_dot(label))),
+                    None => dot.push_str(&format!("    block{} -> block{};\n", i, target))
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// labels for the successors of a terminator: branch condition or switch case values
+fn edge_labels(terminator: LLVMValueRef) -> Vec<String> {
+    unsafe {
+        if !LLVMIsABranchInst(terminator).is_null() && LLVMIsConditional(terminator) != 0 {
+            let cond = operand_name(LLVMGetCondition(terminator));
+            vec![format!("{}: true", cond), format!("{}: false", cond)]
+        }else if !LLVMIsASwitchInst(terminator).is_null() {
+            // operands are condition, default, then (value, destination) pairs
+            let count = LLVMGetNumSuccessors(terminator);
+            let mut labels = vec!["default".to_string()];
+            for i in 1..count {
+                labels.push(operand_name(LLVMGetOperand(terminator, 2 * i)));
+            }
+            labels
+        }else{
+            Vec::new()
+        }
+    }
+}
+
+fn operand_name(value: LLVMValueRef) -> String {
+    let name = Value::from_ptr(value).get_name();
+    if name.is_empty() {
+        Value::from_ptr(value).print_to_string().trim().to_string()
+    }else{
+        format!("%{}", name)
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' | '\\' | '{' | '}' | '<' | '>' | '|' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            _ => escaped.push(c)
         }
-        types
     }
+    escaped
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+extern crate libc;
+
+use self::libc::c_void;
+use std::ptr;
+
+// layouts of the "shadow-stack" gc strategy, see llvm/CodeGen/ShadowStackGCLowering.cpp
+#[repr(C)]
+#[derive(Debug)]
+pub struct FrameMap {
+    pub num_roots: i32,
+    // roots with metadata come first
+    pub num_meta: i32,
+    pub meta: [*const c_void; 0],
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct StackEntry {
+    pub next: *mut StackEntry,
+    pub map: *const FrameMap,
+    pub roots: [*mut c_void; 0],
+}
+
+// walks the frames of JIT code compiled with the shadow-stack gc strategy
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowStack {
+    head: *mut *mut StackEntry
+}
+
+impl ShadowStack {
+    // address of the llvm_gc_root_chain global, e.g. from Engine::get_global_value_address
+    pub unsafe fn from_address(address: u64) -> ShadowStack {
+        ShadowStack {
+            head: address as usize as *mut *mut StackEntry
+        }
+    }
+
+    pub fn frames_count(&self) -> usize {
+        let mut count = 0;
+        let mut entry = unsafe { *self.head };
+        while !entry.is_null() {
+            count += 1;
+            entry = unsafe { (*entry).next };
+        }
+        count
+    }
+
+    // calls visitor with the address of every root slot and its metadata (null if none),
+    // innermost frame first; the visitor may update the slot, e.g. when moving objects
+    pub fn visit_roots<F>(&self, mut visitor: F)
+        where F: FnMut(*mut *mut c_void, *const c_void)
+    {
+        let mut entry = unsafe { *self.head };
+        while !entry.is_null() {
+            unsafe {
+                let map = &*(*entry).map;
+                let roots = (*entry).roots.as_mut_ptr();
+                let meta = map.meta.as_ptr();
+                for i in 0..map.num_roots as isize {
+                    let metadata = if i < map.num_meta as isize { *meta.offset(i) } else { ptr::null() };
+                    visitor(roots.offset(i), metadata);
+                }
+                entry = (*entry).next;
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+extern crate libc;
+
+use self::libc::c_void;
+
+// Rust functions callable from JIT code through a global mapping or LLVMAddSymbol
+pub trait HostFunction: Copy {
+    fn params_count() -> u32;
+    fn address(self) -> *mut c_void;
+}
+
+macro_rules! impl_host_function {
+    ($count:expr; $($param:ident),*) => {
+        impl<R $(, $param)*> HostFunction for extern "C" fn($($param),*) -> R {
+            #[inline]
+            fn params_count() -> u32 {
+                $count
+            }
+
+            #[inline]
+            fn address(self) -> *mut c_void {
+                self as *mut c_void
+            }
+        }
+    };
+}
+
+impl_host_function!(0;);
+impl_host_function!(1; A);
+impl_host_function!(2; A, B);
+impl_host_function!(3; A, B, C);
+impl_host_function!(4; A, B, C, D);
+impl_host_function!(5; A, B, C, D, E);
+impl_host_function!(6; A, B, C, D, E, F);
\ No newline at end of file
@@ -0,0 +1,47 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use metadata::Metadata;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Instruction {
+    llvm_instruction: LLVMValueRef
+}
+
+impl Instruction {
+    pub fn from_ptr(inst_ptr: LLVMValueRef) -> Instruction {
+        Instruction {
+            llvm_instruction: inst_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMValueRef {
+        self.llvm_instruction
+    }
+
+    #[inline]
+    pub fn has_metadata(&self) -> bool {
+        unsafe { LLVMHasMetadata(self.llvm_instruction) != 0 }
+    }
+
+    #[inline]
+    pub fn get_metadata(&self, kind_id: u32) -> Option<Metadata> {
+        let md = unsafe { LLVMGetMetadata(self.llvm_instruction, kind_id) };
+        if md.is_null() {
+            None
+        }else{
+            Some(Metadata::from_ptr(md))
+        }
+    }
+
+    #[inline]
+    pub fn set_metadata(&self, kind_id: u32, node: Metadata){
+        unsafe { LLVMSetMetadata(self.llvm_instruction, kind_id, node.as_ref()) }
+    }
+
+    #[inline]
+    pub fn remove_metadata(&self, kind_id: u32){
+        unsafe { LLVMSetMetadata(self.llvm_instruction, kind_id, 0 as LLVMValueRef) }
+    }
+}
\ No newline at end of file
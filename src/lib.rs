@@ -9,36 +9,105 @@ mod engine;
 mod phi;
 mod struct_type;
 mod cstring_manager;
+mod metadata;
+mod instruction;
+mod type_info;
+mod value;
+mod target_data;
+mod target_machine;
+mod target_init;
+mod llvm_string;
+mod error;
+mod basic_block;
+mod verify;
+pub mod analysis;
+mod orc;
+mod host_function;
+mod mcjit;
+mod object_cache;
+mod alias;
+mod gc;
+mod const_expr;
 
 pub use self::llvm_sys::core::*;
 pub use self::llvm_sys::prelude::*;
-pub use self::builder::Builder;
+pub use self::builder::{Builder, AsmDialect};
 pub use self::module::Module;
 pub use self::function::Function;
 pub use self::context::Context;
 pub use self::phi::Phi;
-pub use self::engine::{Engine, FuncallResult};
+pub use self::engine::{Engine, EngineModule, FuncallResult};
 pub use self::struct_type::Struct;
+pub use self::metadata::Metadata;
+pub use self::instruction::Instruction;
+pub use self::type_info::{TypeInfo, TypeKind};
+pub use self::value::Value;
+pub use self::target_data::TargetData;
+pub use self::target_machine::{Target, TargetMachine};
+pub use self::target_init::TargetArch;
+pub use self::llvm_string::LLVMString;
+pub use self::error::Error;
+pub use self::basic_block::BasicBlock;
+pub use self::verify::{VerifyError, Diagnostic};
+pub use self::orc::OrcJit;
+pub use self::host_function::HostFunction;
+pub use self::mcjit::{McJitOptions, MemoryManager};
+pub use self::object_cache::ObjectCache;
+pub use self::alias::Alias;
+pub use self::gc::{ShadowStack, StackEntry, FrameMap};
+pub use self::const_expr::ConstExpr;
 pub use self::llvm_sys::*;
 
 #[allow(non_snake_case)]
 pub mod LLVM {
     use llvm_sys::core::*;
-    use llvm_sys::target;
     use llvm_sys::prelude::*;
-    use std::os::raw::c_uint;
+    use llvm_sys::support::{LLVMAddSymbol, LLVMLoadLibraryPermanently, LLVMSearchForAddressOfSymbol};
+    use std::os::raw::{c_char, c_uint};
+    use libc::c_void;
+    use cstring_manager::CStringManager;
+    use host_function::HostFunction;
+    use error::Error;
+    use std::io;
+    use context::{check_int_width, check_int_string, check_real_string};
+
+    pub use target_init::{initialize_native_target, initialize_all_targets, initialize_target};
 
     pub fn initialize(){
-        unsafe {
-            if target::LLVM_InitializeNativeTarget() != 0 {
-                panic!("Could not initialise target");
-            }
-            if target::LLVM_InitializeNativeAsmPrinter() != 0 {
-                panic!("Could not initialise ASM Printer");
-            }
+        if let Err(msg) = initialize_native_target() {
+            panic!("{}", msg);
+        }
+    }
+
+    // make a host symbol visible to the symbol resolution of every JIT
+    pub fn add_symbol(name: &str, address: *mut c_void){
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMAddSymbol(name_ptr, address) }
+    }
+
+    #[inline]
+    pub fn add_host_function<F: HostFunction>(name: &str, host_function: F){
+        add_symbol(name, host_function.address())
+    }
+
+    // symbols of the library become visible to the JIT symbol resolution
+    pub fn load_library_permanently(path: &str) -> Result<(), Error> {
+        let path_ptr = CStringManager::new_cstring_as_ptr(path);
+        let result = unsafe { LLVMLoadLibraryPermanently(path_ptr) };
+        if result == 1 { // error
+            Err(Error::Io(io::Error::new(io::ErrorKind::Other, format!("could not load library '{}'", path))))
+        }else{ // success
+            Ok(())
         }
     }
 
+    // searches symbols added with add_symbol and all permanently loaded libraries
+    pub fn search_for_address_of_symbol(name: &str) -> Option<*mut c_void> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let address = unsafe { LLVMSearchForAddressOfSymbol(name_ptr) };
+        if address.is_null() { None } else { Some(address) }
+    }
+
     pub mod Type {
         use super::*;
 
@@ -128,60 +197,71 @@ pub mod LLVM {
         use super::*;
 
         #[inline]
-        pub fn SInt(num_bits: c_uint, val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMIntType(num_bits), val, 1) }
+        pub fn SInt(num_bits: c_uint, val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMIntType(num_bits), val as u64, 1) }
         }
         #[inline]
         pub fn UInt(num_bits: c_uint, val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMIntType(num_bits), val, 0) }
         }
         #[inline]
-        pub fn SInt1(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt1Type(), val, 1) }
+        pub fn SInt1(val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMInt1Type(), val as u64, 1) }
         }
         #[inline]
         pub fn UInt1(val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMInt1Type(), val, 0) }
         }
         #[inline]
-        pub fn SInt8(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt8Type(), val, 1) }
+        pub fn SInt8(val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMInt8Type(), val as u64, 1) }
         }
         #[inline]
         pub fn UInt8(val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMInt8Type(), val, 0) }
         }
         #[inline]
-        pub fn SInt16(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt16Type(), val, 1) }
+        pub fn SInt16(val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMInt16Type(), val as u64, 1) }
         }
         #[inline]
         pub fn UInt16(val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMInt16Type(), val, 0) }
         }
         #[inline]
-        pub fn SInt32(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt32Type(), val, 1) }
+        pub fn SInt32(val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMInt32Type(), val as u64, 1) }
         }
         #[inline]
         pub fn UInt32(val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMInt32Type(), val, 0) }
         }
         #[inline]
-        pub fn SInt64(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt64Type(), val, 1) }
+        pub fn SInt64(val: i64) -> LLVMValueRef {
+            unsafe { LLVMConstInt(LLVMInt64Type(), val as u64, 1) }
         }
         #[inline]
         pub fn UInt64(val: u64) -> LLVMValueRef {
             unsafe { LLVMConstInt(LLVMInt64Type(), val, 0) }
         }
         #[inline]
-        pub fn SInt128(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt128Type(), val, 1) }
+        pub fn SInt128(val: i128) -> LLVMValueRef {
+            UInt128(val as u128)
+        }
+        #[inline]
+        pub fn UInt128(val: u128) -> LLVMValueRef {
+            let words = [val as u64, (val >> 64) as u64];
+            unsafe { LLVMConstIntOfArbitraryPrecision(LLVMInt128Type(), 2, words.as_ptr()) }
+        }
+        #[inline]
+        pub fn IntOfArbitraryPrecision(num_bits: c_uint, words: &[u64]) -> Result<LLVMValueRef, Error> {
+            check_int_width(num_bits)?;
+            Ok(unsafe { LLVMConstIntOfArbitraryPrecision(LLVMIntType(num_bits), words.len() as c_uint, words.as_ptr()) })
         }
         #[inline]
-        pub fn UInt128(val: u64) -> LLVMValueRef {
-            unsafe { LLVMConstInt(LLVMInt128Type(), val, 0) }
+        pub fn IntOfString(num_bits: c_uint, text: &str, radix: u8) -> Result<LLVMValueRef, Error> {
+            check_int_string(num_bits, text, radix)?;
+            Ok(unsafe { LLVMConstIntOfStringAndSize(LLVMIntType(num_bits), text.as_ptr() as *const c_char, text.len() as c_uint, radix) })
         }
 
         #[inline]
@@ -208,6 +288,11 @@ pub mod LLVM {
         pub fn PPCFP128(val: f64) -> LLVMValueRef {
             unsafe { LLVMConstReal(LLVMPPCFP128Type(), val) }
         }
+        #[inline]
+        pub fn RealOfString(typ: LLVMTypeRef, text: &str) -> Result<LLVMValueRef, Error> {
+            check_real_string(typ, text)?;
+            Ok(unsafe { LLVMConstRealOfStringAndSize(typ, text.as_ptr() as *const c_char, text.len() as c_uint) })
+        }
     }
 }
 
@@ -238,3 +323,19 @@ macro_rules! fn_type {
         }
     )
 }
+
+#[macro_export]
+macro_rules! fn_type_in_context {
+    ($context:expr; $result_type:expr) => (
+        $context.FunctionType($result_type, &mut [], false)
+    );
+    ($context:expr; $result_type:expr,,,) => (
+        $context.FunctionType($result_type, &mut [], true)
+    );
+    ($context:expr; $result_type:expr, $( $param_type:expr ),* ) => (
+        $context.FunctionType($result_type, &mut [ $( $param_type ),* ], false)
+    );
+    ($context:expr; $result_type:expr, $( $param_type:expr ),* ,,,) => (
+        $context.FunctionType($result_type, &mut [ $( $param_type ),* ], true)
+    )
+}
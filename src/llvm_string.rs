@@ -0,0 +1,57 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::LLVMDisposeMessage;
+use std::ffi::CStr;
+use std::fmt;
+use std::os::raw::c_char;
+
+// owns a message buffer allocated by LLVM and releases it with LLVMDisposeMessage
+pub struct LLVMString {
+    ptr: *mut c_char
+}
+
+impl LLVMString {
+    pub fn from_ptr(ptr: *mut c_char) -> LLVMString {
+        LLVMString {
+            ptr: ptr
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const c_char {
+        self.ptr
+    }
+
+    pub fn as_c_str(&self) -> &CStr {
+        if self.ptr.is_null() {
+            Default::default()
+        }else{
+            unsafe { CStr::from_ptr(self.ptr) }
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.as_c_str().to_bytes().is_empty()
+    }
+}
+
+impl fmt::Display for LLVMString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_c_str().to_string_lossy())
+    }
+}
+
+impl fmt::Debug for LLVMString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.as_c_str())
+    }
+}
+
+impl Drop for LLVMString {
+    #[inline]
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            unsafe { LLVMDisposeMessage(self.ptr) }
+        }
+    }
+}
\ No newline at end of file
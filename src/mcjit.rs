@@ -0,0 +1,133 @@
+extern crate llvm_sys;
+extern crate libc;
+
+use self::llvm_sys::prelude::*;
+use self::llvm_sys::execution_engine::*;
+use self::llvm_sys::target_machine::LLVMCodeModel;
+use self::libc::c_void;
+use std::ffi::{CStr, CString};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::os::raw::{c_char, c_uint};
+
+// sections requested by MCJIT are allocated and made executable by the implementor.
+// MCJIT can not recover from a failed allocation: returning null from allocate_code_section
+// or allocate_data_section makes LLVM call report_fatal_error, which ends the process.
+// a cap on JIT memory therefore has to be enforced before code is generated, e.g. by
+// limiting the modules added to the engine.
+// a panic in any of these methods is caught at the LLVM boundary. it is treated as a failed
+// allocation (so it ends the process as well), a finalize_memory error, or ignored in destroy.
+pub trait MemoryManager {
+    fn allocate_code_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str) -> *mut u8;
+    fn allocate_data_section(&mut self, size: usize, alignment: u32, section_id: u32, section_name: &str, is_read_only: bool) -> *mut u8;
+    fn finalize_memory(&mut self) -> Result<(), String>;
+    fn destroy(&mut self) {}
+}
+
+// panics must not unwind into LLVM
+fn catch_panic<T, F: FnOnce() -> T>(f: F, on_panic: T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(on_panic)
+}
+
+extern "C" fn allocate_code_section(opaque: *mut c_void, size: usize, alignment: c_uint, section_id: c_uint, section_name: *const c_char) -> *mut u8 {
+    let manager = unsafe { &mut *(opaque as *mut Box<dyn MemoryManager>) };
+    let name = unsafe { CStr::from_ptr(section_name) }.to_string_lossy();
+    catch_panic(|| manager.allocate_code_section(size, alignment, section_id, &name), ptr::null_mut())
+}
+
+extern "C" fn allocate_data_section(opaque: *mut c_void, size: usize, alignment: c_uint, section_id: c_uint, section_name: *const c_char, is_read_only: LLVMBool) -> *mut u8 {
+    let manager = unsafe { &mut *(opaque as *mut Box<dyn MemoryManager>) };
+    let name = unsafe { CStr::from_ptr(section_name) }.to_string_lossy();
+    catch_panic(|| manager.allocate_data_section(size, alignment, section_id, &name, is_read_only != 0), ptr::null_mut())
+}
+
+extern "C" fn finalize_memory(opaque: *mut c_void, error: *mut *mut c_char) -> LLVMBool {
+    let manager = unsafe { &mut *(opaque as *mut Box<dyn MemoryManager>) };
+    match catch_panic(|| manager.finalize_memory(), Err("memory manager panicked in finalize_memory".to_string())) {
+        Ok(_) => 0,
+        Err(msg) => {
+            // LLVM releases the message with free()
+            let msg = CString::new(msg.replace('\0', "")).unwrap();
+            unsafe { *error = libc::strdup(msg.as_ptr()) };
+            1
+        }
+    }
+}
+
+extern "C" fn destroy(opaque: *mut c_void) {
+    let mut manager = unsafe { Box::from_raw(opaque as *mut Box<dyn MemoryManager>) };
+    catch_panic(|| manager.destroy(), ());
+}
+
+pub struct McJitOptions {
+    opt_level: u32,
+    code_model: LLVMCodeModel,
+    no_frame_pointer_elim: bool,
+    enable_fast_isel: bool,
+    memory_manager: Option<Box<dyn MemoryManager>>,
+}
+
+impl McJitOptions {
+    pub fn new() -> McJitOptions {
+        McJitOptions {
+            opt_level: 0,
+            code_model: LLVMCodeModel::LLVMCodeModelJITDefault,
+            no_frame_pointer_elim: false,
+            enable_fast_isel: false,
+            memory_manager: None,
+        }
+    }
+
+    pub fn opt_level(mut self, opt_level: u32) -> McJitOptions {
+        self.opt_level = opt_level;
+        self
+    }
+
+    pub fn code_model(mut self, code_model: LLVMCodeModel) -> McJitOptions {
+        self.code_model = code_model;
+        self
+    }
+
+    pub fn no_frame_pointer_elim(mut self, no_frame_pointer_elim: bool) -> McJitOptions {
+        self.no_frame_pointer_elim = no_frame_pointer_elim;
+        self
+    }
+
+    pub fn enable_fast_isel(mut self, enable_fast_isel: bool) -> McJitOptions {
+        self.enable_fast_isel = enable_fast_isel;
+        self
+    }
+
+    pub fn memory_manager<M: MemoryManager + 'static>(mut self, memory_manager: M) -> McJitOptions {
+        self.memory_manager = Some(Box::new(memory_manager));
+        self
+    }
+
+    // the memory manager is returned separately so that it is only turned into an LLVM
+    // memory manager right before the engine takes it over (see create_llvm_memory_manager)
+    pub(crate) fn into_llvm_options(self) -> (LLVMMCJITCompilerOptions, Option<Box<dyn MemoryManager>>) {
+        let mut options: LLVMMCJITCompilerOptions = unsafe { mem::zeroed() };
+        unsafe { LLVMInitializeMCJITCompilerOptions(&mut options, mem::size_of::<LLVMMCJITCompilerOptions>()) };
+        options.OptLevel = self.opt_level;
+        options.CodeModel = self.code_model;
+        options.NoFramePointerElim = self.no_frame_pointer_elim as LLVMBool;
+        options.EnableFastISel = self.enable_fast_isel as LLVMBool;
+        (options, self.memory_manager)
+    }
+}
+
+// the returned manager owns memory_manager and must be passed to LLVMCreateMCJITCompilerForModule,
+// which disposes it together with the engine (or right away when the engine can not be created)
+pub(crate) fn create_llvm_memory_manager(memory_manager: Box<dyn MemoryManager>) -> LLVMMCJITMemoryManagerRef {
+    let opaque = Box::into_raw(Box::new(memory_manager)) as *mut c_void;
+    unsafe {
+        LLVMCreateSimpleMCJITMemoryManager(opaque, allocate_code_section, allocate_data_section, finalize_memory, Some(destroy))
+    }
+}
+
+impl Default for McJitOptions {
+    fn default() -> McJitOptions {
+        McJitOptions::new()
+    }
+}
\ No newline at end of file
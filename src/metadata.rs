@@ -0,0 +1,78 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use std::os::raw::{c_char, c_uint};
+use std::slice;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Metadata {
+    llvm_metadata: LLVMValueRef
+}
+
+impl Metadata {
+    pub fn new_string(context: LLVMContextRef, string: &str) -> Metadata {
+        let md = unsafe { LLVMMDStringInContext(context, string.as_ptr() as *const c_char, string.len() as c_uint) };
+        Metadata {
+            llvm_metadata: md
+        }
+    }
+
+    pub fn new_node(context: LLVMContextRef, values: &mut [LLVMValueRef]) -> Metadata {
+        let md = unsafe { LLVMMDNodeInContext(context, values.as_mut_ptr(), values.len() as c_uint) };
+        Metadata {
+            llvm_metadata: md
+        }
+    }
+
+    pub fn from_ptr(md_ptr: LLVMValueRef) -> Metadata {
+        Metadata {
+            llvm_metadata: md_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMValueRef {
+        self.llvm_metadata
+    }
+
+    #[inline]
+    pub fn is_string(&self) -> bool {
+        unsafe { !LLVMIsAMDString(self.llvm_metadata).is_null() }
+    }
+
+    #[inline]
+    pub fn is_node(&self) -> bool {
+        unsafe { !LLVMIsAMDNode(self.llvm_metadata).is_null() }
+    }
+
+    pub fn get_string(&self) -> Option<String> {
+        if !self.is_string() {
+            return None;
+        }
+        let mut len: c_uint = 0;
+        let ptr = unsafe { LLVMGetMDString(self.llvm_metadata, &mut len) };
+        if ptr.is_null() {
+            return None;
+        }
+        let bytes = unsafe { slice::from_raw_parts(ptr as *const u8, len as usize) };
+        Some(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    #[inline]
+    pub fn operands_count(&self) -> u32 {
+        if self.is_node() {
+            unsafe { LLVMGetMDNodeNumOperands(self.llvm_metadata) }
+        }else{
+            0
+        }
+    }
+
+    pub fn get_operands(&self) -> Vec<LLVMValueRef> {
+        let count = self.operands_count() as usize;
+        let mut operands: Vec<LLVMValueRef> = vec![0 as LLVMValueRef; count];
+        if count > 0 {
+            unsafe { LLVMGetMDNodeOperands(self.llvm_metadata, operands.as_mut_ptr()) }
+        }
+        operands
+    }
+}
\ No newline at end of file
@@ -5,11 +5,28 @@ extern crate llvm_sys;
 use self::llvm_sys::core::*;
 use self::llvm_sys::prelude::*;
 use self::llvm_sys::analysis::{LLVMVerifyModule, LLVMVerifierFailureAction};
+use self::llvm_sys::ir_reader::LLVMParseIRInContext;
 use cstring_manager::CStringManager;
-use std::ffi::CString;
+use llvm_string::LLVMString;
+use error::Error;
+use verify::VerifyError;
+use std::ffi::CStr;
 use std::os::raw::c_char;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::mem;
+use std::str;
 use function;
 use engine::Engine;
+use mcjit::McJitOptions;
+use orc::OrcJit;
+use object_cache::ObjectCache;
+use metadata::Metadata;
+use alias::Alias;
+use struct_type::Struct;
+use target_data::{TargetData, check_data_layout};
+use target_machine::TargetMachine;
 
 #[derive(Debug)]
 pub struct Module {
@@ -33,10 +50,42 @@ impl Module {
         }
     }
 
+    pub fn parse_ir(context: LLVMContextRef, ir: &str) -> Result<Module, Error> {
+        let buf_name_ptr = CStringManager::new_cstring_as_ptr("ir");
+        let mut module: LLVMModuleRef = 0 as LLVMModuleRef;
+        let mut error: *mut c_char = 0 as *mut c_char;
+        let result = unsafe {
+            // the memory buffer is owned by the parser
+            let buffer = LLVMCreateMemoryBufferWithMemoryRangeCopy(ir.as_ptr() as *const c_char, ir.len(), buf_name_ptr);
+            LLVMParseIRInContext(context, buffer, &mut module, &mut error)
+        };
+        if result == 1 { // error
+            Err(Error::Parse(LLVMString::from_ptr(error).to_string()))
+        }else{ // success
+            Ok(Module {
+                llvm_module: module
+            })
+        }
+    }
+
+    // takes ownership of the module
+    pub fn from_ptr(module: LLVMModuleRef) -> Module {
+        Module {
+            llvm_module: module
+        }
+    }
+
     pub fn as_ref(&self) -> LLVMModuleRef {
         self.llvm_module
     }
 
+    // give up ownership, e.g. to a jit that disposes the module itself
+    pub fn into_ref(self) -> LLVMModuleRef {
+        let module = self.llvm_module;
+        mem::forget(self);
+        module
+    }
+
     pub fn add_function(&self, name: &str, function_type: LLVMTypeRef) -> function::Function {
         function::Function::new(self.llvm_module, name, function_type)
     }
@@ -58,15 +107,156 @@ impl Module {
         }
     }
 
-    pub fn verify(&self) -> Result<(), String> {
+    // finds any named struct of the module's context, not only those used in this module
+    pub fn get_struct_by_name(&self, name: &str) -> Option<Struct> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let struct_type = unsafe { LLVMGetTypeByName(self.llvm_module, name_ptr) };
+        if struct_type.is_null() {
+            None
+        }else{
+            Some(Struct::from_ptr(struct_type))
+        }
+    }
+
+    #[inline]
+    pub fn add_global(&self, typ: LLVMTypeRef, name: &str) -> LLVMValueRef {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMAddGlobal(self.llvm_module, typ, name_ptr) }
+    }
+
+    pub fn add_global_with_initializer(&self, name: &str, initializer: LLVMValueRef, is_constant: bool) -> LLVMValueRef {
+        let global = self.add_global(unsafe { LLVMTypeOf(initializer) }, name);
+        unsafe {
+            LLVMSetInitializer(global, initializer);
+            LLVMSetGlobalConstant(global, is_constant as LLVMBool);
+        }
+        global
+    }
+
+    #[inline]
+    pub fn get_named_global(&self, name: &str) -> Option<LLVMValueRef> {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let global = unsafe { LLVMGetNamedGlobal(self.llvm_module, name_ptr) };
+        if global.is_null() { None } else { Some(global) }
+    }
+
+    pub fn get_globals(&self) -> Vec<LLVMValueRef> {
+        let mut globals = Vec::new();
+        let mut global = unsafe { LLVMGetFirstGlobal(self.llvm_module) };
+        while !global.is_null() {
+            globals.push(global);
+            global = unsafe { LLVMGetNextGlobal(global) };
+        }
+        globals
+    }
+
+    // alias_type is the pointer type of the alias, usually the type of the aliasee
+    // (comdats are not supported: the C API of LLVM 6 can neither create nor assign them)
+    pub fn add_alias(&self, alias_type: LLVMTypeRef, aliasee: LLVMValueRef, name: &str) -> Alias {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let alias = unsafe { LLVMAddAlias(self.llvm_module, alias_type, aliasee, name_ptr) };
+        Alias::from_ptr(alias)
+    }
+
+    // replaces the module level assembly
+    #[inline]
+    pub fn set_inline_asm(&self, asm: &str){
+        let asm_ptr = CStringManager::new_cstring_as_ptr(asm);
+        unsafe { LLVMSetModuleInlineAsm(self.llvm_module, asm_ptr) }
+    }
+
+    // appends on a new line, like Module::appendModuleInlineAsm in LLVM.
+    // the C API of LLVM 6 can neither append nor read module level assembly, so the current
+    // assembly is read back from the printed module: each call prints the whole module
+    pub fn append_inline_asm(&self, asm: &str){
+        let mut module_asm = String::new();
+        for line in self.print_module_to_string().lines() {
+            if line.starts_with("module asm \"") && line.ends_with('"') {
+                module_asm.push_str(&unescape_ir_string(&line[12..line.len() - 1]));
+                module_asm.push('\n');
+            }
+        }
+        module_asm.push_str(asm);
+        self.set_inline_asm(&module_asm);
+    }
+
+    pub fn get_functions(&self) -> Vec<function::Function> {
+        let mut functions = Vec::new();
+        let mut func = unsafe { LLVMGetFirstFunction(self.llvm_module) };
+        while !func.is_null() {
+            functions.push(function::Function::from_ptr(func));
+            func = unsafe { LLVMGetNextFunction(func) };
+        }
+        functions
+    }
+
+    #[inline]
+    pub fn set_target_triple(&self, triple: &str){
+        let triple_ptr = CStringManager::new_cstring_as_ptr(triple);
+        unsafe { LLVMSetTarget(self.llvm_module, triple_ptr) }
+    }
+
+    pub fn target_triple(&self) -> String {
+        let ptr = unsafe { LLVMGetTarget(self.llvm_module) };
+        unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+    }
+
+    // set target triple and data layout of the host machine
+    pub fn configure_for_host(&self) -> Result<(), Error> {
+        let target_machine = TargetMachine::host()?;
+        self.set_target_triple(&target_machine.get_triple());
+        self.set_data_layout(&target_machine.create_target_data().get_data_layout())
+    }
+
+    pub fn set_data_layout(&self, data_layout: &str) -> Result<(), Error> {
+        check_data_layout(data_layout)?;
+        let layout_ptr = CStringManager::new_cstring_as_ptr(data_layout);
+        unsafe { LLVMSetDataLayout(self.llvm_module, layout_ptr) }
+        Ok(())
+    }
+
+    pub fn data_layout(&self) -> String {
+        let ptr = unsafe { LLVMGetDataLayoutStr(self.llvm_module) };
+        unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() }
+    }
+
+    #[inline]
+    pub fn get_target_data(&self) -> TargetData {
+        TargetData::from_valid_layout(&self.data_layout())
+    }
+
+    #[inline]
+    pub fn add_named_metadata_operand(&self, name: &str, value: Metadata){
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMAddNamedMetadataOperand(self.llvm_module, name_ptr, value.as_ref()) }
+    }
+
+    #[inline]
+    pub fn named_metadata_operands_count(&self, name: &str) -> u32 {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMGetNamedMetadataNumOperands(self.llvm_module, name_ptr) }
+    }
+
+    pub fn get_named_metadata_operands(&self, name: &str) -> Vec<Metadata> {
+        let count = self.named_metadata_operands_count(name) as usize;
+        let mut operands: Vec<LLVMValueRef> = vec![0 as LLVMValueRef; count];
+        if count > 0 {
+            let name_ptr = CStringManager::new_cstring_as_ptr(name);
+            unsafe { LLVMGetNamedMetadataOperands(self.llvm_module, name_ptr, operands.as_mut_ptr()) }
+        }
+        operands.into_iter().map(Metadata::from_ptr).collect()
+    }
+
+    pub fn verify(&self) -> Result<(), Error> {
         let mut error: *mut c_char = 0 as *mut c_char;
         let ok = unsafe {
             let buf: *mut *mut c_char = &mut error;
             LLVMVerifyModule(self.llvm_module, LLVMVerifierFailureAction::LLVMReturnStatusAction, buf)
         };
+        // the message buffer is allocated even on success
+        let message = LLVMString::from_ptr(error);
         if ok == 1 { // error
-            let err_msg = unsafe { CString::from_raw(error).into_string().unwrap() };
-            Err(err_msg)
+            Err(Error::Verify(VerifyError::from_message(self.llvm_module, &message.to_string())))
         }else{ // success
             Ok(())
         }
@@ -78,13 +268,11 @@ impl Module {
     }
 
     pub fn print_module_to_string(&self) -> String {
-        let ptr = unsafe { LLVMPrintModuleToString(self.llvm_module) };
-        let string = unsafe { CString::from_raw(ptr).into_string().unwrap() };
-        unsafe { LLVMDisposeMessage(ptr); }
-        string
+        let string = LLVMString::from_ptr(unsafe { LLVMPrintModuleToString(self.llvm_module) });
+        string.to_string()
     }
 
-    pub fn print_module_to_file(&self, filename: &str) -> Result<(), String> {
+    pub fn print_module_to_file(&self, filename: &str) -> Result<(), Error> {
         let fname_ptr = CStringManager::new_cstring_as_ptr(filename);
         let mut error: *mut c_char = 0 as *mut c_char;
         let ok = unsafe {
@@ -92,23 +280,49 @@ impl Module {
             LLVMPrintModuleToFile(self.llvm_module, fname_ptr, buf)
         };
         if ok == 1 { // error
-            let err_msg = unsafe { CString::from_raw(error).into_string().unwrap() };
-            unsafe { LLVMDisposeMessage(error) }
-            Err(err_msg)
+            Err(Error::Io(io::Error::new(io::ErrorKind::Other, LLVMString::from_ptr(error).to_string())))
         }else{ // success
             Ok(())
         }
     }
 
+    // write cfg.<function>.dot for every defined function
+    pub fn write_cfg_dots<P: AsRef<Path>>(&self, dir: P) -> Result<(), Error> {
+        for function in self.get_functions() {
+            if function.is_declaration() {
+                continue;
+            }
+            let path = dir.as_ref().join(format!("cfg.{}.dot", function.get_name()));
+            let mut file = File::create(path)?;
+            file.write_all(function.to_dot().as_bytes())?;
+        }
+        Ok(())
+    }
+
     #[inline]
-    pub fn create_interpreter(&self) -> Result<Engine, String> {
+    pub fn create_interpreter(&self) -> Result<Engine, Error> {
         Engine::create_interpreter(self.as_ref())
     }
 
     #[inline]
-    pub fn create_jit_engine(&self) -> Result<Engine, String> {
+    pub fn create_jit_engine(&self) -> Result<Engine, Error> {
         Engine::create_jit_engine(self.as_ref())
     }
+
+    #[inline]
+    pub fn create_jit_engine_with_options(&self, options: McJitOptions) -> Result<Engine, Error> {
+        Engine::create_jit_engine_with_options(self.as_ref(), options)
+    }
+
+    // like create_jit_engine, but skips code generation when the cache has an object for this module.
+    // the LLVM 6 C API can not hand objects to MCJIT, so the cached object runs on an OrcJit.
+    // the flag tells whether the object came from the cache
+    pub fn create_cached_jit(&self, cache: &ObjectCache) -> Result<(OrcJit, bool), Error> {
+        let jit = OrcJit::new()?;
+        let (object, hit) = cache.get_or_compile(self, jit.target_machine())?;
+        jit.add_object_file(&object)?;
+        Ok((jit, hit))
+    }
 }
 
 impl Drop for Module {
@@ -116,4 +330,23 @@ impl Drop for Module {
     fn drop(&mut self) {
         unsafe { LLVMDisposeModule(self.llvm_module) }
     }
+}
+
+// reverses the \XX escapes of strings printed in LLVM IR
+fn unescape_ir_string(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 2 < bytes.len() {
+            if let Some(byte) = str::from_utf8(&bytes[i + 1..i + 3]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                unescaped.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        unescaped.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
 }
\ No newline at end of file
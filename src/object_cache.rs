@@ -0,0 +1,83 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::bit_writer::LLVMWriteBitcodeToMemoryBuffer;
+use self::llvm_sys::target_machine::LLVMCodeGenFileType;
+use module::Module;
+use target_machine::TargetMachine;
+use error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::slice;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// compiled object files stored in a directory, keyed by module bitcode and the settings
+// of the target machine which compiled them
+#[derive(Debug, Clone)]
+pub struct ObjectCache {
+    dir: PathBuf
+}
+
+impl ObjectCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<ObjectCache, Error> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(ObjectCache {
+            dir: dir.as_ref().to_path_buf()
+        })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn key(&self, module: &Module, target_machine: &TargetMachine) -> String {
+        let bitcode = unsafe {
+            let buffer = LLVMWriteBitcodeToMemoryBuffer(module.as_ref());
+            let start = LLVMGetBufferStart(buffer) as *const u8;
+            let bitcode = slice::from_raw_parts(start, LLVMGetBufferSize(buffer)).to_vec();
+            LLVMDisposeMemoryBuffer(buffer);
+            bitcode
+        };
+        let settings = format!("{}\0{}\0{}\0{:?}\0{:?}\0{:?}", target_machine.get_triple(), target_machine.get_cpu(),
+                               target_machine.get_feature_string(), target_machine.get_opt_level(),
+                               target_machine.get_reloc_mode(), target_machine.get_code_model());
+        // FNV-1a, stable across builds unlike std's DefaultHasher
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in bitcode.iter().chain(b"\0").chain(settings.as_bytes()) {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{:016x}", hash)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.o", key))
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        fs::read(self.path(key)).ok()
+    }
+
+    pub fn put(&self, key: &str, object: &[u8]) -> Result<(), Error> {
+        // write to a temporary file first so readers never see a partial object.
+        // the name is unique to this process and call, so concurrent writers do not share it
+        let temp_path = self.dir.join(format!("{}.o.{}.{}.tmp", key, process::id(), TEMP_COUNTER.fetch_add(1, Ordering::SeqCst)));
+        fs::write(&temp_path, object)?;
+        fs::rename(&temp_path, self.path(key))?;
+        Ok(())
+    }
+
+    // object code of the module, and whether it came from the cache
+    pub fn get_or_compile(&self, module: &Module, target_machine: &TargetMachine) -> Result<(Vec<u8>, bool), Error> {
+        let key = self.key(module, target_machine);
+        if let Some(object) = self.get(&key) {
+            return Ok((object, true));
+        }
+        let object = target_machine.emit_to_memory(module, LLVMCodeGenFileType::LLVMObjectFile)?;
+        self.put(&key, &object)?;
+        Ok((object, false))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+extern crate llvm_sys;
+extern crate libc;
+
+use self::llvm_sys::orc::*;
+use self::llvm_sys::support::LLVMLoadLibraryPermanently;
+use self::llvm_sys::target_machine::*;
+use self::llvm_sys::core::LLVMCreateMemoryBufferWithMemoryRangeCopy;
+use self::libc::c_void;
+use cstring_manager::CStringManager;
+use target_machine::{Target, TargetMachine};
+use module::Module;
+use object_cache::ObjectCache;
+use error::Error;
+use LLVM;
+use std::ffi::CStr;
+use std::str;
+use std::os::raw::c_char;
+use std::sync::Once;
+
+static LOAD_PROCESS_SYMBOLS: Once = Once::new();
+
+// resolves symbols that are not defined by any module of the jit from the host process
+extern "C" fn resolve_host_symbol(name: *const c_char, _ctx: *mut c_void) -> u64 {
+    let name = unsafe { CStr::from_ptr(name) }.to_bytes();
+    // symbols are mangled with a leading underscore on Darwin
+    let name = if cfg!(target_os = "macos") && name.starts_with(b"_") { &name[1..] } else { name };
+    match str::from_utf8(name).ok().and_then(LLVM::search_for_address_of_symbol) {
+        Some(address) => address as u64,
+        None => 0
+    }
+}
+
+#[derive(Debug)]
+pub struct OrcJit {
+    llvm_jit_stack: LLVMOrcJITStackRef,
+    // same settings as the machine owned by the jit stack, used to compile cached objects
+    target_machine: TargetMachine
+}
+
+impl OrcJit {
+    pub fn new() -> Result<OrcJit, Error> {
+        let target_machine = OrcJit::create_target_machine()?;
+        Ok(OrcJit::with_target_machine(target_machine))
+    }
+
+    fn create_target_machine() -> Result<TargetMachine, Error> {
+        TargetMachine::new(&Target::default_triple(), "", "",
+                           LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+                           LLVMRelocMode::LLVMRelocDefault,
+                           LLVMCodeModel::LLVMCodeModelJITDefault)
+    }
+
+    pub fn with_target_machine(target_machine: TargetMachine) -> OrcJit {
+        LOAD_PROCESS_SYMBOLS.call_once(|| {
+            // make the symbols of the host process visible to LLVMSearchForAddressOfSymbol
+            unsafe { LLVMLoadLibraryPermanently(0 as *const c_char); }
+        });
+        let cache_target_machine = target_machine.duplicate();
+        // the jit stack takes ownership of the target machine
+        let stack = unsafe { LLVMOrcCreateInstance(target_machine.into_ref()) };
+        OrcJit {
+            llvm_jit_stack: stack,
+            target_machine: cache_target_machine
+        }
+    }
+
+    // a machine with the settings the jit compiles with
+    pub fn target_machine(&self) -> &TargetMachine {
+        &self.target_machine
+    }
+
+    pub fn as_ref(&self) -> LLVMOrcJITStackRef {
+        self.llvm_jit_stack
+    }
+
+    // functions are compiled on their first call
+    pub fn add_module(&self, module: Module) -> Result<LLVMOrcModuleHandle, Error> {
+        let mut handle: LLVMOrcModuleHandle = 0;
+        let result = unsafe {
+            let shared_module = LLVMOrcMakeSharedModule(module.into_ref());
+            let result = LLVMOrcAddLazilyCompiledIR(self.llvm_jit_stack, &mut handle, shared_module, Some(resolve_host_symbol), 0 as *mut c_void);
+            LLVMOrcDisposeSharedModuleRef(shared_module);
+            result
+        };
+        self.check(result).map(|_| handle)
+    }
+
+    pub fn add_module_eagerly(&self, module: Module) -> Result<LLVMOrcModuleHandle, Error> {
+        let mut handle: LLVMOrcModuleHandle = 0;
+        let result = unsafe {
+            let shared_module = LLVMOrcMakeSharedModule(module.into_ref());
+            let result = LLVMOrcAddEagerlyCompiledIR(self.llvm_jit_stack, &mut handle, shared_module, Some(resolve_host_symbol), 0 as *mut c_void);
+            LLVMOrcDisposeSharedModuleRef(shared_module);
+            result
+        };
+        self.check(result).map(|_| handle)
+    }
+
+    pub fn add_object_file(&self, object: &[u8]) -> Result<LLVMOrcModuleHandle, Error> {
+        let buf_name_ptr = CStringManager::new_cstring_as_ptr("object");
+        let mut handle: LLVMOrcModuleHandle = 0;
+        let result = unsafe {
+            // the memory buffer is owned by the jit
+            let buffer = LLVMCreateMemoryBufferWithMemoryRangeCopy(object.as_ptr() as *const c_char, object.len(), buf_name_ptr);
+            LLVMOrcAddObjectFile(self.llvm_jit_stack, &mut handle, buffer, Some(resolve_host_symbol), 0 as *mut c_void)
+        };
+        self.check(result).map(|_| handle)
+    }
+
+    // skips code generation when the cache has an object for the same bitcode and target settings.
+    // the flag tells whether the object came from the cache
+    pub fn add_module_with_cache(&self, module: Module, cache: &ObjectCache) -> Result<(LLVMOrcModuleHandle, bool), Error> {
+        let (object, hit) = cache.get_or_compile(&module, &self.target_machine)?;
+        self.add_object_file(&object).map(|handle| (handle, hit))
+    }
+
+    pub fn remove_module(&self, handle: LLVMOrcModuleHandle) -> Result<(), Error> {
+        let result = unsafe { LLVMOrcRemoveModule(self.llvm_jit_stack, handle) };
+        self.check(result)
+    }
+
+    pub fn get_mangled_symbol(&self, name: &str) -> String {
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        let mut mangled: *mut c_char = 0 as *mut c_char;
+        unsafe {
+            LLVMOrcGetMangledSymbol(self.llvm_jit_stack, &mut mangled, name_ptr);
+            let string = CStr::from_ptr(mangled).to_string_lossy().into_owned();
+            LLVMOrcDisposeMangledSymbol(mangled);
+            string
+        }
+    }
+
+    // address of a function or global variable by its unmangled name
+    pub fn get_symbol_address(&self, name: &str) -> Option<u64> {
+        let mangled_ptr = CStringManager::new_cstring_as_ptr(&self.get_mangled_symbol(name));
+        let mut address: LLVMOrcTargetAddress = 0;
+        let result = unsafe { LLVMOrcGetSymbolAddress(self.llvm_jit_stack, &mut address, mangled_ptr) };
+        match result {
+            LLVMOrcErrorCode::LLVMOrcErrSuccess if address != 0 => Some(address),
+            _ => None
+        }
+    }
+
+    // falls back to host symbols and permanently loaded libraries
+    pub fn get_symbol_address_or_host(&self, name: &str) -> Option<u64> {
+        self.get_symbol_address(name).or_else(|| LLVM::search_for_address_of_symbol(name).map(|address| address as u64))
+    }
+
+    fn check(&self, result: LLVMOrcErrorCode) -> Result<(), Error> {
+        match result {
+            LLVMOrcErrorCode::LLVMOrcErrSuccess => Ok(()),
+            _ => {
+                let message = unsafe { CStr::from_ptr(LLVMOrcGetErrorMsg(self.llvm_jit_stack)) };
+                Err(Error::Jit(message.to_string_lossy().into_owned()))
+            }
+        }
+    }
+}
+
+impl Drop for OrcJit {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { LLVMOrcDisposeInstance(self.llvm_jit_stack); }
+    }
+}
\ No newline at end of file
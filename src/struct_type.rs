@@ -3,7 +3,9 @@ extern crate llvm_sys;
 use self::llvm_sys::core::*;
 use self::llvm_sys::prelude::*;
 use cstring_manager::CStringManager;
+use type_info::TypeInfo;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Struct {
     struct_type: LLVMTypeRef
 }
@@ -26,6 +28,12 @@ impl Struct {
         }
     }
 
+    pub fn from_ptr(struct_ptr: LLVMTypeRef) -> Struct {
+        Struct {
+            struct_type: struct_ptr
+        }
+    }
+
     #[inline]
     pub fn new_const_struct(constant_values: &mut [LLVMValueRef], packed: bool) -> LLVMValueRef {
         unsafe { LLVMConstStruct(constant_values.as_mut_ptr(), constant_values.len() as u32, if packed {1}else{0}) }
@@ -39,4 +47,36 @@ impl Struct {
     pub fn set_body(&self, fields: &mut [LLVMTypeRef], packed: bool){
         unsafe { LLVMStructSetBody(self.struct_type, fields.as_mut_ptr(), fields.len() as u32, if packed {1}else{0}) }
     }
+
+    // None for literal structs
+    #[inline]
+    pub fn name(&self) -> Option<String> {
+        TypeInfo::from_ptr(self.struct_type).struct_name()
+    }
+
+    // named structs stay opaque until set_body
+    #[inline]
+    pub fn is_opaque(&self) -> bool {
+        unsafe { LLVMIsOpaqueStruct(self.struct_type) != 0 }
+    }
+
+    #[inline]
+    pub fn is_packed(&self) -> bool {
+        unsafe { LLVMIsPackedStruct(self.struct_type) != 0 }
+    }
+
+    #[inline]
+    pub fn field_count(&self) -> u32 {
+        unsafe { LLVMCountStructElementTypes(self.struct_type) }
+    }
+
+    #[inline]
+    pub fn field_types(&self) -> Vec<LLVMTypeRef> {
+        TypeInfo::from_ptr(self.struct_type).struct_field_types()
+    }
+
+    #[inline]
+    pub fn new_const_named_struct(&self, constant_values: &mut [LLVMValueRef]) -> LLVMValueRef {
+        unsafe { LLVMConstNamedStruct(self.struct_type, constant_values.as_mut_ptr(), constant_values.len() as u32) }
+    }
 }
\ No newline at end of file
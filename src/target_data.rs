@@ -0,0 +1,257 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::prelude::*;
+use self::llvm_sys::target::*;
+use self::llvm_sys::target_machine::LLVMCreateTargetDataLayout;
+use cstring_manager::CStringManager;
+use target_machine::TargetMachine;
+use llvm_string::LLVMString;
+use error::Error;
+
+#[derive(Debug)]
+pub struct TargetData {
+    llvm_target_data: LLVMTargetDataRef
+}
+
+impl TargetData {
+    pub fn new(data_layout: &str) -> Result<TargetData, Error> {
+        check_data_layout(data_layout)?;
+        Ok(TargetData::from_valid_layout(data_layout))
+    }
+
+    // for layouts LLVM has already parsed, e.g. the one of a module
+    pub(crate) fn from_valid_layout(data_layout: &str) -> TargetData {
+        let layout_ptr = CStringManager::new_cstring_as_ptr(data_layout);
+        let target_data = unsafe { LLVMCreateTargetData(layout_ptr) };
+        TargetData {
+            llvm_target_data: target_data
+        }
+    }
+
+    pub fn from_target_machine(target_machine: &TargetMachine) -> TargetData {
+        let target_data = unsafe { LLVMCreateTargetDataLayout(target_machine.as_ref()) };
+        TargetData {
+            llvm_target_data: target_data
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMTargetDataRef {
+        self.llvm_target_data
+    }
+
+    pub fn get_data_layout(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMCopyStringRepOfTargetData(self.llvm_target_data) }).to_string()
+    }
+
+    #[inline]
+    pub fn pointer_size(&self) -> u32 {
+        unsafe { LLVMPointerSize(self.llvm_target_data) }
+    }
+
+    #[inline]
+    pub fn int_ptr_type(&self, context: LLVMContextRef) -> LLVMTypeRef {
+        unsafe { LLVMIntPtrTypeInContext(context, self.llvm_target_data) }
+    }
+
+    #[inline]
+    pub fn size_of_type_in_bits(&self, typ: LLVMTypeRef) -> u64 {
+        unsafe { LLVMSizeOfTypeInBits(self.llvm_target_data, typ) }
+    }
+
+    #[inline]
+    pub fn store_size_of_type(&self, typ: LLVMTypeRef) -> u64 {
+        unsafe { LLVMStoreSizeOfType(self.llvm_target_data, typ) }
+    }
+
+    // ABI size in bytes, including tail padding
+    #[inline]
+    pub fn size_of_type(&self, typ: LLVMTypeRef) -> u64 {
+        unsafe { LLVMABISizeOfType(self.llvm_target_data, typ) }
+    }
+
+    #[inline]
+    pub fn abi_alignment(&self, typ: LLVMTypeRef) -> u32 {
+        unsafe { LLVMABIAlignmentOfType(self.llvm_target_data, typ) }
+    }
+
+    #[inline]
+    pub fn preferred_alignment(&self, typ: LLVMTypeRef) -> u32 {
+        unsafe { LLVMPreferredAlignmentOfType(self.llvm_target_data, typ) }
+    }
+
+    #[inline]
+    pub fn offset_of_element(&self, struct_type: LLVMTypeRef, index: u32) -> u64 {
+        unsafe { LLVMOffsetOfElement(self.llvm_target_data, struct_type, index) }
+    }
+
+    #[inline]
+    pub fn element_at_offset(&self, struct_type: LLVMTypeRef, offset: u64) -> u32 {
+        unsafe { LLVMElementAtOffset(self.llvm_target_data, struct_type, offset) }
+    }
+}
+
+impl Drop for TargetData {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { LLVMDisposeTargetData(self.llvm_target_data) }
+    }
+}
+
+// LLVM calls report_fatal_error on a malformed layout, so check it the way
+// DataLayout::parseSpecifier of LLVM 6 reads it
+pub(crate) fn check_data_layout(data_layout: &str) -> Result<(), Error> {
+    parse_data_layout(data_layout).map_err(|reason| Error::Parse(format!("invalid data layout '{}': {}", data_layout, reason)))
+}
+
+fn parse_data_layout(data_layout: &str) -> Result<(), String> {
+    let mut desc = data_layout;
+    while !desc.is_empty() {
+        let (specification, rest_of_desc) = split_layout(desc, '-')?;
+        desc = rest_of_desc;
+        let (token, mut rest) = split_layout(specification, ':')?;
+
+        if token == "ni" {
+            loop {
+                let (address_space, rest_of_spec) = split_layout(rest, ':')?;
+                rest = rest_of_spec;
+                if layout_int(address_space)? == 0 {
+                    return Err("address space 0 can never be non-integral".to_string());
+                }
+                if rest.is_empty() {
+                    break;
+                }
+            }
+            continue;
+        }
+
+        let specifier = match token.chars().next() {
+            Some(specifier) => specifier,
+            None => return Err(format!("empty specifier in '{}'", specification))
+        };
+        let token = &token[specifier.len_utf8()..];
+        match specifier {
+            // 's' is ignored by LLVM for backward compatibility
+            's' | 'E' | 'e' => {},
+            'p' => {
+                let address_space = if token.is_empty() { 0 } else { layout_int(token)? };
+                if address_space >= 1 << 24 {
+                    return Err("address space must be a 24bit integer".to_string());
+                }
+                if rest.is_empty() {
+                    return Err("missing pointer size".to_string());
+                }
+                let (size, rest_of_spec) = split_layout(rest, ':')?;
+                rest = rest_of_spec;
+                if layout_bytes(size)? == 0 {
+                    return Err("pointer size of 0 bytes".to_string());
+                }
+                if rest.is_empty() {
+                    return Err("missing pointer alignment".to_string());
+                }
+                let (abi_align, rest_of_spec) = split_layout(rest, ':')?;
+                rest = rest_of_spec;
+                let abi_align = layout_bytes(abi_align)?;
+                if !abi_align.is_power_of_two() {
+                    return Err("pointer ABI alignment must be a power of 2".to_string());
+                }
+                let mut pref_align = abi_align;
+                if !rest.is_empty() {
+                    pref_align = layout_bytes(split_layout(rest, ':')?.0)?;
+                    if !pref_align.is_power_of_two() {
+                        return Err("pointer preferred alignment must be a power of 2".to_string());
+                    }
+                }
+                if pref_align < abi_align {
+                    return Err("preferred alignment can not be less than the ABI alignment".to_string());
+                }
+            },
+            'i' | 'v' | 'f' | 'a' => {
+                let size = if token.is_empty() { 0 } else { layout_int(token)? };
+                if specifier == 'a' && size != 0 {
+                    return Err("sized aggregate specification".to_string());
+                }
+                if size >= 1 << 24 {
+                    return Err("bit width must be a 24bit integer".to_string());
+                }
+                if rest.is_empty() {
+                    return Err(format!("missing alignment in '{}'", specification));
+                }
+                let (abi_align, rest_of_spec) = split_layout(rest, ':')?;
+                rest = rest_of_spec;
+                let abi_align = layout_bytes(abi_align)?;
+                if specifier != 'a' && abi_align == 0 {
+                    return Err("ABI alignment must be > 0 for non-aggregate types".to_string());
+                }
+                let mut pref_align = abi_align;
+                if !rest.is_empty() {
+                    pref_align = layout_bytes(split_layout(rest, ':')?.0)?;
+                }
+                if abi_align >= 1 << 16 || pref_align >= 1 << 16 {
+                    return Err("alignment must be a 16bit integer".to_string());
+                }
+                if pref_align < abi_align {
+                    return Err("preferred alignment can not be less than the ABI alignment".to_string());
+                }
+            },
+            'n' => {
+                let mut width = token;
+                loop {
+                    if layout_int(width)? == 0 {
+                        return Err("zero width native integer type".to_string());
+                    }
+                    if rest.is_empty() {
+                        break;
+                    }
+                    let (next_width, rest_of_spec) = split_layout(rest, ':')?;
+                    width = next_width;
+                    rest = rest_of_spec;
+                }
+            },
+            'S' => {
+                layout_bytes(token)?;
+            },
+            'A' => {
+                if layout_int(token)? >= 1 << 24 {
+                    return Err("address space must be a 24bit integer".to_string());
+                }
+            },
+            'm' => {
+                if !token.is_empty() {
+                    return Err("unexpected characters after mangling specifier".to_string());
+                }
+                match rest {
+                    "e" | "o" | "m" | "w" | "x" => {},
+                    _ => return Err(format!("unknown mangling '{}'", rest))
+                }
+            },
+            _ => return Err(format!("unknown specifier '{}'", specifier))
+        }
+    }
+    Ok(())
+}
+
+fn split_layout(text: &str, separator: char) -> Result<(&str, &str), String> {
+    if text.is_empty() {
+        return Err("empty specification".to_string());
+    }
+    match text.find(separator) {
+        Some(index) if index + 1 == text.len() => Err(format!("trailing '{}'", separator)),
+        Some(index) => Ok((&text[..index], &text[index + 1..])),
+        None => Ok((text, ""))
+    }
+}
+
+fn layout_int(text: &str) -> Result<u32, String> {
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(format!("'{}' is not a number", text));
+    }
+    text.parse().map_err(|_| format!("'{}' does not fit in an unsigned int", text))
+}
+
+fn layout_bytes(text: &str) -> Result<u32, String> {
+    let bits = layout_int(text)?;
+    if bits % 8 != 0 {
+        return Err(format!("{} bits is not a multiple of a byte", bits));
+    }
+    Ok(bits / 8)
+}
\ No newline at end of file
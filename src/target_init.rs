@@ -0,0 +1,191 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::target;
+use self::llvm_sys::target_machine::LLVMGetTargetFromName;
+use cstring_manager::CStringManager;
+use error::Error;
+use std::sync::{Mutex, Once};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetArch {
+    X86,
+    AArch64,
+    ARM,
+    Mips,
+    PowerPC,
+}
+
+// per-target entry points are only declared for backends which LLVM 6 builds by default,
+// each behind its own cargo feature (all off by default) so that an LLVM configured without
+// it still links. the native target and initialize_all_targets() need no feature.
+// experimental backends (RISC-V, WebAssembly) are reachable through initialize_all_targets()
+// when LLVM was configured with them.
+#[cfg(feature = "target-x86")]
+extern "C" {
+    fn LLVMInitializeX86TargetInfo();
+    fn LLVMInitializeX86Target();
+    fn LLVMInitializeX86TargetMC();
+    fn LLVMInitializeX86AsmPrinter();
+    fn LLVMInitializeX86AsmParser();
+    fn LLVMInitializeX86Disassembler();
+}
+
+#[cfg(feature = "target-aarch64")]
+extern "C" {
+    fn LLVMInitializeAArch64TargetInfo();
+    fn LLVMInitializeAArch64Target();
+    fn LLVMInitializeAArch64TargetMC();
+    fn LLVMInitializeAArch64AsmPrinter();
+    fn LLVMInitializeAArch64AsmParser();
+    fn LLVMInitializeAArch64Disassembler();
+}
+
+#[cfg(feature = "target-arm")]
+extern "C" {
+    fn LLVMInitializeARMTargetInfo();
+    fn LLVMInitializeARMTarget();
+    fn LLVMInitializeARMTargetMC();
+    fn LLVMInitializeARMAsmPrinter();
+    fn LLVMInitializeARMAsmParser();
+    fn LLVMInitializeARMDisassembler();
+}
+
+#[cfg(feature = "target-mips")]
+extern "C" {
+    fn LLVMInitializeMipsTargetInfo();
+    fn LLVMInitializeMipsTarget();
+    fn LLVMInitializeMipsTargetMC();
+    fn LLVMInitializeMipsAsmPrinter();
+    fn LLVMInitializeMipsAsmParser();
+    fn LLVMInitializeMipsDisassembler();
+}
+
+#[cfg(feature = "target-powerpc")]
+extern "C" {
+    fn LLVMInitializePowerPCTargetInfo();
+    fn LLVMInitializePowerPCTarget();
+    fn LLVMInitializePowerPCTargetMC();
+    fn LLVMInitializePowerPCAsmPrinter();
+    fn LLVMInitializePowerPCAsmParser();
+    fn LLVMInitializePowerPCDisassembler();
+}
+
+impl TargetArch {
+    // name under which the target registers itself
+    fn target_name(&self) -> &'static str {
+        match *self {
+            TargetArch::X86     => "x86-64",
+            TargetArch::AArch64 => "aarch64",
+            TargetArch::ARM     => "arm",
+            TargetArch::Mips    => "mips",
+            TargetArch::PowerPC => "ppc64",
+        }
+    }
+
+    fn feature_name(&self) -> &'static str {
+        match *self {
+            TargetArch::X86     => "target-x86",
+            TargetArch::AArch64 => "target-aarch64",
+            TargetArch::ARM     => "target-arm",
+            TargetArch::Mips    => "target-mips",
+            TargetArch::PowerPC => "target-powerpc",
+        }
+    }
+
+    // None when the target's feature is disabled
+    fn init_functions(&self) -> Option<[unsafe extern "C" fn(); 6]> {
+        match *self {
+            #[cfg(feature = "target-x86")]
+            TargetArch::X86 => Some([LLVMInitializeX86TargetInfo, LLVMInitializeX86Target, LLVMInitializeX86TargetMC,
+                                   LLVMInitializeX86AsmPrinter, LLVMInitializeX86AsmParser, LLVMInitializeX86Disassembler]),
+            #[cfg(feature = "target-aarch64")]
+            TargetArch::AArch64 => Some([LLVMInitializeAArch64TargetInfo, LLVMInitializeAArch64Target, LLVMInitializeAArch64TargetMC,
+                                   LLVMInitializeAArch64AsmPrinter, LLVMInitializeAArch64AsmParser, LLVMInitializeAArch64Disassembler]),
+            #[cfg(feature = "target-arm")]
+            TargetArch::ARM => Some([LLVMInitializeARMTargetInfo, LLVMInitializeARMTarget, LLVMInitializeARMTargetMC,
+                                   LLVMInitializeARMAsmPrinter, LLVMInitializeARMAsmParser, LLVMInitializeARMDisassembler]),
+            #[cfg(feature = "target-mips")]
+            TargetArch::Mips => Some([LLVMInitializeMipsTargetInfo, LLVMInitializeMipsTarget, LLVMInitializeMipsTargetMC,
+                                   LLVMInitializeMipsAsmPrinter, LLVMInitializeMipsAsmParser, LLVMInitializeMipsDisassembler]),
+            #[cfg(feature = "target-powerpc")]
+            TargetArch::PowerPC => Some([LLVMInitializePowerPCTargetInfo, LLVMInitializePowerPCTarget, LLVMInitializePowerPCTargetMC,
+                                   LLVMInitializePowerPCAsmPrinter, LLVMInitializePowerPCAsmParser, LLVMInitializePowerPCDisassembler]),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+    fn once(&self) -> &'static Once {
+        match *self {
+            TargetArch::X86     => &X86_INIT,
+            TargetArch::AArch64 => &AARCH64_INIT,
+            TargetArch::ARM     => &ARM_INIT,
+            TargetArch::Mips    => &MIPS_INIT,
+            TargetArch::PowerPC => &POWERPC_INIT,
+        }
+    }
+}
+
+// outcome of the first native initialisation, None until it ran
+static NATIVE_INIT: Mutex<Option<Result<(), String>>> = Mutex::new(None);
+
+static ALL_INIT: Once = Once::new();
+
+static X86_INIT: Once = Once::new();
+static AARCH64_INIT: Once = Once::new();
+static ARM_INIT: Once = Once::new();
+static MIPS_INIT: Once = Once::new();
+static POWERPC_INIT: Once = Once::new();
+
+pub fn initialize_native_target() -> Result<(), Error> {
+    let mut native_init = NATIVE_INIT.lock().unwrap_or_else(|err| err.into_inner());
+    if native_init.is_none() {
+        *native_init = Some(init_native_target());
+    }
+    native_init.clone().unwrap().map_err(Error::Target)
+}
+
+fn init_native_target() -> Result<(), String> {
+    unsafe {
+        if target::LLVM_InitializeNativeTarget() != 0 {
+            return Err("Could not initialise target".to_string());
+        }
+        if target::LLVM_InitializeNativeAsmPrinter() != 0 {
+            return Err("Could not initialise ASM Printer".to_string());
+        }
+        if target::LLVM_InitializeNativeAsmParser() != 0 {
+            return Err("Could not initialise ASM Parser".to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn initialize_all_targets() {
+    ALL_INIT.call_once(|| unsafe {
+        target::LLVM_InitializeAllTargetInfos();
+        target::LLVM_InitializeAllTargets();
+        target::LLVM_InitializeAllTargetMCs();
+        target::LLVM_InitializeAllAsmPrinters();
+        target::LLVM_InitializeAllAsmParsers();
+        target::LLVM_InitializeAllDisassemblers();
+    });
+}
+
+pub fn initialize_target(arch: TargetArch) -> Result<(), Error> {
+    let init_functions = match arch.init_functions() {
+        Some(init_functions) => init_functions,
+        None => return Err(Error::Target(format!("target '{}' is disabled, enable the '{}' feature", arch.target_name(), arch.feature_name())))
+    };
+    arch.once().call_once(|| {
+        for init in init_functions.iter() {
+            unsafe { init() }
+        }
+    });
+
+    let name_ptr = CStringManager::new_cstring_as_ptr(arch.target_name());
+    if unsafe { LLVMGetTargetFromName(name_ptr) }.is_null() {
+        Err(Error::Target(format!("Could not initialise target '{}'", arch.target_name())))
+    }else{
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,191 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::target_machine::*;
+use self::llvm_sys::prelude::*;
+use cstring_manager::CStringManager;
+use target_data::TargetData;
+use module::Module;
+use llvm_string::LLVMString;
+use error::Error;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::slice;
+use std::mem;
+
+#[derive(Debug)]
+pub struct Target {
+    llvm_target: LLVMTargetRef
+}
+
+impl Target {
+    pub fn from_triple(triple: &str) -> Result<Target, Error> {
+        let triple_ptr = CStringManager::new_cstring_as_ptr(triple);
+        let mut target: LLVMTargetRef = 0 as LLVMTargetRef;
+        let mut error: *mut c_char = 0 as *mut c_char;
+        let result = unsafe { LLVMGetTargetFromTriple(triple_ptr, &mut target, &mut error) };
+        if result == 1 { // error
+            Err(Error::Target(LLVMString::from_ptr(error).to_string()))
+        }else{ // success
+            Ok(Target {
+                llvm_target: target
+            })
+        }
+    }
+
+    pub fn host() -> Result<Target, Error> {
+        Target::from_triple(&Target::default_triple())
+    }
+
+    // there is no host_cpu_name() or host_cpu_features(): LLVMGetHostCPUName and
+    // LLVMGetHostCPUFeatures first appear in LLVM 7, and llvm-sys 60 binds LLVM 6.
+    // host machines use the default CPU and features for the default triple instead.
+    pub fn default_triple() -> String {
+        LLVMString::from_ptr(unsafe { LLVMGetDefaultTargetTriple() }).to_string()
+    }
+
+    pub fn as_ref(&self) -> LLVMTargetRef {
+        self.llvm_target
+    }
+
+    pub fn get_name(&self) -> String {
+        unsafe { CStr::from_ptr(LLVMGetTargetName(self.llvm_target)).to_string_lossy().into_owned() }
+    }
+
+    pub fn get_description(&self) -> String {
+        unsafe { CStr::from_ptr(LLVMGetTargetDescription(self.llvm_target)).to_string_lossy().into_owned() }
+    }
+
+    #[inline]
+    pub fn has_jit(&self) -> bool {
+        unsafe { LLVMTargetHasJIT(self.llvm_target) != 0 }
+    }
+
+    #[inline]
+    pub fn has_asm_backend(&self) -> bool {
+        unsafe { LLVMTargetHasAsmBackend(self.llvm_target) != 0 }
+    }
+
+    pub fn create_target_machine(&self, triple: &str, cpu: &str, features: &str, opt_level: LLVMCodeGenOptLevel, reloc_mode: LLVMRelocMode, code_model: LLVMCodeModel) -> TargetMachine {
+        let triple_ptr = CStringManager::new_cstring_as_ptr(triple);
+        let cpu_ptr = CStringManager::new_cstring_as_ptr(cpu);
+        let features_ptr = CStringManager::new_cstring_as_ptr(features);
+        let target_machine = unsafe { LLVMCreateTargetMachine(self.llvm_target, triple_ptr, cpu_ptr, features_ptr, opt_level, reloc_mode, code_model) };
+        TargetMachine {
+            llvm_target_machine: target_machine,
+            opt_level: opt_level,
+            reloc_mode: reloc_mode,
+            code_model: code_model
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TargetMachine {
+    llvm_target_machine: LLVMTargetMachineRef,
+    // not readable through the C API, so remembered from creation
+    opt_level: LLVMCodeGenOptLevel,
+    reloc_mode: LLVMRelocMode,
+    code_model: LLVMCodeModel
+}
+
+impl TargetMachine {
+    pub fn new(triple: &str, cpu: &str, features: &str, opt_level: LLVMCodeGenOptLevel, reloc_mode: LLVMRelocMode, code_model: LLVMCodeModel) -> Result<TargetMachine, Error> {
+        let target = Target::from_triple(triple)?;
+        Ok(target.create_target_machine(triple, cpu, features, opt_level, reloc_mode, code_model))
+    }
+
+    // generic CPU without extra features, as LLVM 6 can not report the host CPU (see Target::default_triple)
+    pub fn host() -> Result<TargetMachine, Error> {
+        TargetMachine::new(&Target::default_triple(), "", "",
+                           LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+                           LLVMRelocMode::LLVMRelocDefault,
+                           LLVMCodeModel::LLVMCodeModelDefault)
+    }
+
+    pub fn as_ref(&self) -> LLVMTargetMachineRef {
+        self.llvm_target_machine
+    }
+
+    pub fn into_ref(self) -> LLVMTargetMachineRef {
+        let target_machine = self.llvm_target_machine;
+        mem::forget(self);
+        target_machine
+    }
+
+    pub fn get_triple(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMGetTargetMachineTriple(self.llvm_target_machine) }).to_string()
+    }
+
+    pub fn get_cpu(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMGetTargetMachineCPU(self.llvm_target_machine) }).to_string()
+    }
+
+    pub fn get_feature_string(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMGetTargetMachineFeatureString(self.llvm_target_machine) }).to_string()
+    }
+
+    #[inline]
+    pub fn get_opt_level(&self) -> LLVMCodeGenOptLevel {
+        self.opt_level
+    }
+
+    #[inline]
+    pub fn get_reloc_mode(&self) -> LLVMRelocMode {
+        self.reloc_mode
+    }
+
+    #[inline]
+    pub fn get_code_model(&self) -> LLVMCodeModel {
+        self.code_model
+    }
+
+    // a new target machine with the same settings
+    pub fn duplicate(&self) -> TargetMachine {
+        let target = Target {
+            llvm_target: unsafe { LLVMGetTargetMachineTarget(self.llvm_target_machine) }
+        };
+        target.create_target_machine(&self.get_triple(), &self.get_cpu(), &self.get_feature_string(),
+                                     self.opt_level, self.reloc_mode, self.code_model)
+    }
+
+    #[inline]
+    pub fn create_target_data(&self) -> TargetData {
+        TargetData::from_target_machine(self)
+    }
+
+    pub fn emit_to_file(&self, module: &Module, filename: &str, file_type: LLVMCodeGenFileType) -> Result<(), Error> {
+        let fname_ptr = CStringManager::new_cstring_as_ptr(filename);
+        let mut error: *mut c_char = 0 as *mut c_char;
+        let result = unsafe { LLVMTargetMachineEmitToFile(self.llvm_target_machine, module.as_ref(), fname_ptr as *mut c_char, file_type, &mut error) };
+        if result == 1 { // error
+            Err(Error::Target(LLVMString::from_ptr(error).to_string()))
+        }else{ // success
+            Ok(())
+        }
+    }
+
+    pub fn emit_to_memory(&self, module: &Module, file_type: LLVMCodeGenFileType) -> Result<Vec<u8>, Error> {
+        let mut error: *mut c_char = 0 as *mut c_char;
+        let mut buffer: LLVMMemoryBufferRef = 0 as LLVMMemoryBufferRef;
+        let result = unsafe { LLVMTargetMachineEmitToMemoryBuffer(self.llvm_target_machine, module.as_ref(), file_type, &mut error, &mut buffer) };
+        if result == 1 { // error
+            Err(Error::Target(LLVMString::from_ptr(error).to_string()))
+        }else{ // success
+            let bytes = unsafe {
+                let start = LLVMGetBufferStart(buffer) as *const u8;
+                let bytes = slice::from_raw_parts(start, LLVMGetBufferSize(buffer)).to_vec();
+                LLVMDisposeMemoryBuffer(buffer);
+                bytes
+            };
+            Ok(bytes)
+        }
+    }
+}
+
+impl Drop for TargetMachine {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { LLVMDisposeTargetMachine(self.llvm_target_machine) }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,174 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use self::llvm_sys::LLVMTypeKind;
+use llvm_string::LLVMString;
+use std::ffi::CStr;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Void,
+    Half,
+    Float,
+    Double,
+    X86FP80,
+    FP128,
+    PPCFP128,
+    Label,
+    Integer,
+    Function,
+    Struct,
+    Array,
+    Pointer,
+    Vector,
+    Metadata,
+    X86MMX,
+    Token,
+}
+
+impl TypeKind {
+    fn from_llvm(kind: LLVMTypeKind) -> TypeKind {
+        match kind {
+            LLVMTypeKind::LLVMVoidTypeKind      => TypeKind::Void,
+            LLVMTypeKind::LLVMHalfTypeKind      => TypeKind::Half,
+            LLVMTypeKind::LLVMFloatTypeKind     => TypeKind::Float,
+            LLVMTypeKind::LLVMDoubleTypeKind    => TypeKind::Double,
+            LLVMTypeKind::LLVMX86_FP80TypeKind  => TypeKind::X86FP80,
+            LLVMTypeKind::LLVMFP128TypeKind     => TypeKind::FP128,
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => TypeKind::PPCFP128,
+            LLVMTypeKind::LLVMLabelTypeKind     => TypeKind::Label,
+            LLVMTypeKind::LLVMIntegerTypeKind   => TypeKind::Integer,
+            LLVMTypeKind::LLVMFunctionTypeKind  => TypeKind::Function,
+            LLVMTypeKind::LLVMStructTypeKind    => TypeKind::Struct,
+            LLVMTypeKind::LLVMArrayTypeKind     => TypeKind::Array,
+            LLVMTypeKind::LLVMPointerTypeKind   => TypeKind::Pointer,
+            LLVMTypeKind::LLVMVectorTypeKind    => TypeKind::Vector,
+            LLVMTypeKind::LLVMMetadataTypeKind  => TypeKind::Metadata,
+            LLVMTypeKind::LLVMX86_MMXTypeKind   => TypeKind::X86MMX,
+            LLVMTypeKind::LLVMTokenTypeKind     => TypeKind::Token,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeInfo {
+    llvm_type: LLVMTypeRef
+}
+
+impl TypeInfo {
+    pub fn from_ptr(type_ptr: LLVMTypeRef) -> TypeInfo {
+        TypeInfo {
+            llvm_type: type_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMTypeRef {
+        self.llvm_type
+    }
+
+    #[inline]
+    pub fn kind(&self) -> TypeKind {
+        TypeKind::from_llvm(unsafe { LLVMGetTypeKind(self.llvm_type) })
+    }
+
+    #[inline]
+    pub fn is_sized(&self) -> bool {
+        unsafe { LLVMTypeIsSized(self.llvm_type) != 0 }
+    }
+
+    //
+    // Integer
+    //
+    #[inline]
+    pub fn int_width(&self) -> u32 {
+        unsafe { LLVMGetIntTypeWidth(self.llvm_type) }
+    }
+
+    //
+    // Pointer, Array and Vector
+    //
+    #[inline]
+    pub fn element_type(&self) -> LLVMTypeRef {
+        unsafe { LLVMGetElementType(self.llvm_type) }
+    }
+
+    #[inline]
+    pub fn array_length(&self) -> u32 {
+        unsafe { LLVMGetArrayLength(self.llvm_type) }
+    }
+
+    #[inline]
+    pub fn vector_size(&self) -> u32 {
+        unsafe { LLVMGetVectorSize(self.llvm_type) }
+    }
+
+    #[inline]
+    pub fn pointer_address_space(&self) -> u32 {
+        unsafe { LLVMGetPointerAddressSpace(self.llvm_type) }
+    }
+
+    //
+    // Struct
+    //
+    pub fn struct_name(&self) -> Option<String> {
+        let name = unsafe { LLVMGetStructName(self.llvm_type) };
+        if name.is_null() {
+            None
+        }else{
+            Some(unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned())
+        }
+    }
+
+    #[inline]
+    pub fn struct_field_count(&self) -> u32 {
+        unsafe { LLVMCountStructElementTypes(self.llvm_type) }
+    }
+
+    pub fn struct_field_types(&self) -> Vec<LLVMTypeRef> {
+        let count = self.struct_field_count() as usize;
+        let mut types: Vec<LLVMTypeRef> = vec![0 as LLVMTypeRef; count];
+        if count > 0 {
+            unsafe { LLVMGetStructElementTypes(self.llvm_type, types.as_mut_ptr()) }
+        }
+        types
+    }
+
+    //
+    // Function
+    //
+    #[inline]
+    pub fn return_type(&self) -> LLVMTypeRef {
+        unsafe { LLVMGetReturnType(self.llvm_type) }
+    }
+
+    #[inline]
+    pub fn param_count(&self) -> u32 {
+        unsafe { LLVMCountParamTypes(self.llvm_type) }
+    }
+
+    pub fn param_types(&self) -> Vec<LLVMTypeRef> {
+        let count = self.param_count() as usize;
+        let mut types: Vec<LLVMTypeRef> = vec![0 as LLVMTypeRef; count];
+        if count > 0 {
+            unsafe { LLVMGetParamTypes(self.llvm_type, types.as_mut_ptr()) }
+        }
+        types
+    }
+
+    #[inline]
+    pub fn is_var_arg(&self) -> bool {
+        unsafe { LLVMIsFunctionVarArg(self.llvm_type) != 0 }
+    }
+
+    pub fn print_to_string(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMPrintTypeToString(self.llvm_type) }).to_string()
+    }
+}
+
+impl fmt::Display for TypeInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.print_to_string())
+    }
+}
\ No newline at end of file
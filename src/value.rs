@@ -0,0 +1,193 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use cstring_manager::CStringManager;
+use type_info::TypeInfo;
+use llvm_string::LLVMString;
+use std::ffi::CStr;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Value {
+    llvm_value: LLVMValueRef
+}
+
+impl Value {
+    pub fn from_ptr(value_ptr: LLVMValueRef) -> Value {
+        Value {
+            llvm_value: value_ptr
+        }
+    }
+
+    pub fn as_ref(&self) -> LLVMValueRef {
+        self.llvm_value
+    }
+
+    pub fn get_name(&self) -> String {
+        let name = unsafe { LLVMGetValueName(self.llvm_value) };
+        if name.is_null() {
+            String::new()
+        }else{
+            unsafe { CStr::from_ptr(name) }.to_string_lossy().into_owned()
+        }
+    }
+
+    #[inline]
+    pub fn set_name(&self, name: &str){
+        let name_ptr = CStringManager::new_cstring_as_ptr(name);
+        unsafe { LLVMSetValueName(self.llvm_value, name_ptr) }
+    }
+
+    #[inline]
+    pub fn get_type(&self) -> LLVMTypeRef {
+        unsafe { LLVMTypeOf(self.llvm_value) }
+    }
+
+    #[inline]
+    pub fn get_type_info(&self) -> TypeInfo {
+        TypeInfo::from_ptr(self.get_type())
+    }
+
+    #[inline]
+    pub fn is_constant(&self) -> bool {
+        unsafe { LLVMIsConstant(self.llvm_value) != 0 }
+    }
+
+    #[inline]
+    pub fn is_undef(&self) -> bool {
+        unsafe { LLVMIsUndef(self.llvm_value) != 0 }
+    }
+
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        unsafe { LLVMIsNull(self.llvm_value) != 0 }
+    }
+
+    #[inline]
+    pub fn is_instruction(&self) -> bool {
+        unsafe { !LLVMIsAInstruction(self.llvm_value).is_null() }
+    }
+
+    //
+    // use list
+    //
+    pub fn get_uses(&self) -> Vec<LLVMUseRef> {
+        let mut uses = Vec::new();
+        let mut use_ref = unsafe { LLVMGetFirstUse(self.llvm_value) };
+        while !use_ref.is_null() {
+            uses.push(use_ref);
+            use_ref = unsafe { LLVMGetNextUse(use_ref) };
+        }
+        uses
+    }
+
+    pub fn get_users(&self) -> Vec<LLVMValueRef> {
+        self.get_uses().into_iter().map(|use_ref| unsafe { LLVMGetUser(use_ref) }).collect()
+    }
+
+    #[inline]
+    pub fn uses_count(&self) -> usize {
+        self.get_uses().len()
+    }
+
+    #[inline]
+    pub fn replace_all_uses_with(&self, new_value: LLVMValueRef){
+        unsafe { LLVMReplaceAllUsesWith(self.llvm_value, new_value) }
+    }
+
+    #[inline]
+    pub fn is_const_int(&self) -> bool {
+        unsafe { !LLVMIsAConstantInt(self.llvm_value).is_null() }
+    }
+
+    #[inline]
+    pub fn is_const_real(&self) -> bool {
+        unsafe { !LLVMIsAConstantFP(self.llvm_value).is_null() }
+    }
+
+    // None unless the value is an integer constant of at most 64 bits
+    pub fn const_zext_value(&self) -> Option<u64> {
+        if self.is_const_int() && self.get_type_info().int_width() <= 64 {
+            Some(unsafe { LLVMConstIntGetZExtValue(self.llvm_value) })
+        }else{
+            None
+        }
+    }
+
+    pub fn const_sext_value(&self) -> Option<i64> {
+        if self.is_const_int() && self.get_type_info().int_width() <= 64 {
+            Some(unsafe { LLVMConstIntGetSExtValue(self.llvm_value) })
+        }else{
+            None
+        }
+    }
+
+    // 64 bit words of an integer constant of any width, least significant first
+    pub fn const_words(&self) -> Option<Vec<u64>> {
+        if !self.is_const_int() {
+            return None;
+        }
+        let width = self.get_type_info().int_width();
+        let mut words = Vec::new();
+        unsafe {
+            let typ = self.get_type();
+            let int64_type = LLVMInt64TypeInContext(LLVMGetTypeContext(typ));
+            for i in 0..(width + 63) / 64 {
+                if width <= 64 {
+                    words.push(LLVMConstIntGetZExtValue(self.llvm_value));
+                    break;
+                }
+                // constant folding keeps the results ConstantInts
+                let shifted = LLVMConstLShr(self.llvm_value, LLVMConstInt(typ, 64 * i as u64, 0));
+                words.push(LLVMConstIntGetZExtValue(LLVMConstTrunc(shifted, int64_type)));
+            }
+        }
+        Some(words)
+    }
+
+    pub fn const_u128_value(&self) -> Option<u128> {
+        if !self.is_const_int() || self.get_type_info().int_width() > 128 {
+            return None;
+        }
+        self.const_words().map(|words| {
+            words.iter().enumerate().fold(0u128, |acc, (i, word)| acc | (*word as u128) << (64 * i))
+        })
+    }
+
+    pub fn const_i128_value(&self) -> Option<i128> {
+        if !self.is_const_int() {
+            return None;
+        }
+        let width = self.get_type_info().int_width();
+        if width > 128 {
+            return None;
+        }
+        // sign extend from the constant's own width
+        self.const_u128_value().map(|value| ((value << (128 - width)) as i128) >> (128 - width))
+    }
+
+    pub fn const_real_value(&self) -> Option<f64> {
+        if self.is_const_real() {
+            let mut loses_info: LLVMBool = 0;
+            Some(unsafe { LLVMConstRealGetDouble(self.llvm_value, &mut loses_info) })
+        }else{
+            None
+        }
+    }
+
+    pub fn print_to_string(&self) -> String {
+        LLVMString::from_ptr(unsafe { LLVMPrintValueToString(self.llvm_value) }).to_string()
+    }
+
+    #[inline]
+    pub fn dump(&self){
+        unsafe { LLVMDumpValue(self.llvm_value) }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.print_to_string())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,158 @@
+extern crate llvm_sys;
+
+use self::llvm_sys::core::*;
+use self::llvm_sys::prelude::*;
+use function::Function;
+use value::Value;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    message: String,
+    details: Vec<String>,
+    function: Option<String>,
+    instruction: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            details: Vec::new(),
+            function: None,
+            instruction: None,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    // instructions and operands printed by the verifier after the message
+    pub fn details(&self) -> &[String] {
+        &self.details
+    }
+
+    pub fn function(&self) -> Option<&str> {
+        self.function.as_ref().map(|name| name.as_str())
+    }
+
+    pub fn instruction(&self) -> Option<&str> {
+        self.instruction.as_ref().map(|inst| inst.as_str())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for detail in self.details.iter() {
+            write!(f, "\n{}", detail)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifyError {
+    message: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl VerifyError {
+    // split the verifier output into diagnostics and locate the offending functions
+    pub fn from_message(module: LLVMModuleRef, message: &str) -> VerifyError {
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for line in message.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let is_detail = line.starts_with(char::is_whitespace) || is_operand(line);
+            if is_detail && !diagnostics.is_empty() {
+                diagnostics.last_mut().unwrap().details.push(line.to_string());
+            }else{
+                diagnostics.push(Diagnostic::new(line));
+            }
+        }
+
+        let instructions = printed_instructions(module);
+        for diagnostic in diagnostics.iter_mut() {
+            for detail in diagnostic.details.iter() {
+                let text = detail.trim();
+                if let Some(&(ref function, ref inst)) = instructions.iter().find(|&&(_, ref inst)| inst == text) {
+                    diagnostic.function = Some(function.clone());
+                    diagnostic.instruction = Some(inst.clone());
+                    break;
+                }
+                if let Some(name) = global_name(text) {
+                    if instructions.iter().any(|&(ref function, _)| function == name) {
+                        diagnostic.function = Some(name.to_string());
+                        break;
+                    }
+                }
+            }
+        }
+
+        VerifyError {
+            message: message.to_string(),
+            diagnostics: diagnostics
+        }
+    }
+
+    // keep only the diagnostics of one function, or all of them if none could be mapped to it
+    pub fn for_function(self, name: &str) -> VerifyError {
+        if !self.diagnostics.iter().any(|diagnostic| diagnostic.function() == Some(name)) {
+            return self;
+        }
+        let diagnostics: Vec<Diagnostic> = self.diagnostics.into_iter().filter(|diagnostic| diagnostic.function() == Some(name)).collect();
+        let message = diagnostics.iter().map(|diagnostic| diagnostic.to_string()).collect::<Vec<String>>().join("\n");
+        VerifyError {
+            message: message,
+            diagnostics: diagnostics
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message.trim_end())
+    }
+}
+
+// operands are printed without indentation, e.g. "label %entry" or "i32 ()* @main"
+fn is_operand(line: &str) -> bool {
+    match line.split_whitespace().last() {
+        Some(token) => line.contains(' ') && (token.starts_with('%') || token.starts_with('@')),
+        None => false
+    }
+}
+
+fn global_name(text: &str) -> Option<&str> {
+    text.split_whitespace().last().and_then(|token| {
+        if token.starts_with('@') { Some(&token[1..]) } else { None }
+    })
+}
+
+// (function name, printed instruction) for every instruction of the module
+fn printed_instructions(module: LLVMModuleRef) -> Vec<(String, String)> {
+    let mut instructions = Vec::new();
+    let mut func = unsafe { LLVMGetFirstFunction(module) };
+    while !func.is_null() {
+        let function = Function::from_ptr(func);
+        let name = function.get_name();
+        for block in function.get_basic_blocks() {
+            for inst in block.get_instructions() {
+                instructions.push((name.clone(), Value::from_ptr(inst).print_to_string().trim().to_string()));
+            }
+        }
+        func = unsafe { LLVMGetNextFunction(func) };
+    }
+    instructions
+}
\ No newline at end of file
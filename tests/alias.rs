@@ -0,0 +1,56 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::mem;
+
+#[test]
+fn test_alias() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("alias");
+
+    // Int32 version_1() and Int32 version_2()
+    let fun_type = fn_type!(context.Int32Type());
+    let version_1 = module.add_function("version_1", fun_type);
+    builder.position_at_end(version_1.append_basic_block("entry"));
+    builder.build_ret(context.SInt32(1));
+
+    let version_2 = module.add_function("version_2", fun_type);
+    builder.position_at_end(version_2.append_basic_block("entry"));
+    builder.build_ret(context.SInt32(2));
+
+    // versioned entry point
+    let alias = module.add_alias(context.PointerType(fun_type), version_1.as_ref(), "entry_point");
+    assert_eq!(alias.get_name(), "entry_point");
+    assert_eq!(alias.get_aliasee(), version_1.as_ref());
+    assert_eq!(alias.get_linkage(), LLVMLinkage::LLVMExternalLinkage);
+
+    alias.set_aliasee(version_2.as_ref());
+    assert_eq!(alias.get_aliasee(), version_2.as_ref());
+    assert!(module.print_module_to_string().contains("@entry_point = alias i32 (), i32 ()* @version_2"));
+
+    let internal_alias = module.add_alias(context.PointerType(fun_type), version_1.as_ref(), "internal_entry");
+    internal_alias.set_linkage(LLVMLinkage::LLVMInternalLinkage);
+    assert_eq!(internal_alias.get_linkage(), LLVMLinkage::LLVMInternalLinkage);
+
+    // Int32 call_entry() { ret entry_point() }
+    let call_func = module.add_function("call_entry", fun_type);
+    builder.position_at_end(call_func.append_basic_block("entry"));
+    builder.build_ret(builder.build_call(alias.as_ref(), &mut []));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    let call_entry: extern "C" fn() -> i32 = unsafe { mem::transmute(engine.get_function_address("call_entry").unwrap() as usize) };
+    assert_eq!(call_entry(), 2);
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use llvm_sys_wrapper::analysis::{Cfg, DominatorTree, Loop};
+
+#[test]
+fn test_fib_dominators() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("fib_analysis");
+
+    // Int64 fib(Int64), as in examples/fib
+    let fun_type = fn_type!(context.Int64Type(), context.Int64Type());
+    let fib_func = module.add_function("fib", fun_type);
+    let entry_block = fib_func.append_basic_block("entry");
+    let else0 = fib_func.append_basic_block("else0");
+    let else1 = fib_func.append_basic_block("else1");
+    let end = fib_func.append_basic_block("end");
+    builder.position_at_end(entry_block);
+
+    let arg = fib_func.get_param(0);
+    let cond = builder.build_icmp_eq(arg, context.UInt64(0));
+    builder.build_cond_br(cond, end, else0);
+
+    builder.position_at_end(else0);
+    let cond = builder.build_icmp_eq(arg, context.UInt64(1));
+    builder.build_cond_br(cond, end, else1);
+
+    builder.position_at_end(else1);
+    let mut args = [builder.build_sub(arg, context.UInt64(2))];
+    let fib_sub2 = builder.build_tail_call(fib_func.as_ref(), &mut args);
+    let mut args = [builder.build_sub(arg, context.UInt64(1))];
+    let fib_sub1 = builder.build_tail_call(fib_func.as_ref(), &mut args);
+    let sum = builder.build_add(fib_sub2, fib_sub1);
+    builder.build_br(end);
+
+    builder.position_at_end(end);
+    let phi = builder.build_phi(context.Int64Type());
+    phi.add_incoming(context.UInt64(0), entry_block);
+    phi.add_incoming(context.UInt64(1), else0);
+    phi.add_incoming(sum, else1);
+    builder.build_ret(phi.as_ref());
+
+    let (entry_block, else0, else1, end) = (BasicBlock::from_ptr(entry_block), BasicBlock::from_ptr(else0), BasicBlock::from_ptr(else1), BasicBlock::from_ptr(end));
+
+    let cfg = Cfg::new(&fib_func);
+    assert_eq!(cfg.entry(), Some(entry_block));
+    assert_eq!(cfg.successors(entry_block), vec![end, else0]);
+    assert_eq!(cfg.predecessors(end), vec![entry_block, else0, else1]);
+    let rpo = cfg.reverse_post_order();
+    assert_eq!(rpo.len(), 4);
+    assert_eq!(rpo[0], entry_block);
+    assert_eq!(rpo[3], end);
+
+    let dominators = DominatorTree::new(&cfg);
+    assert_eq!(dominators.immediate_dominator(entry_block), None);
+    assert_eq!(dominators.immediate_dominator(else0), Some(entry_block));
+    assert_eq!(dominators.immediate_dominator(else1), Some(else0));
+    assert_eq!(dominators.immediate_dominator(end), Some(entry_block));
+    assert!(dominators.dominates(else0, else1));
+    assert!(!dominators.dominates(else1, end));
+    assert_eq!(dominators.children(entry_block), vec![else0, end]);
+
+    // recursion through tail calls is not a loop in the cfg
+    assert!(Loop::find_loops(&cfg, &dominators).is_empty());
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
+
+#[test]
+fn test_loops() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("loop_analysis");
+
+    // Void nested(Int32 n) { for i in 0..n { for j in 0..n {} } }
+    let fun_type = fn_type!(context.VoidType(), context.Int32Type());
+    let function = module.add_function("nested", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let n = function.get_param(0);
+    builder.build_for(context.SInt32(0), n, context.SInt32(1), |b, _| {
+        b.build_for(context.SInt32(0), n, context.SInt32(1), |_, _| {});
+    });
+    builder.build_ret_void();
+
+    // unreachable block
+    let dead_block = function.append_basic_block("dead");
+    builder.position_at_end(dead_block);
+    builder.build_ret_void();
+    let dead_block = BasicBlock::from_ptr(dead_block);
+
+    let cfg = Cfg::new(&function);
+    assert!(!cfg.is_reachable(dead_block));
+    assert!(!cfg.reverse_post_order().contains(&dead_block));
+
+    let dominators = DominatorTree::new(&cfg);
+    assert_eq!(dominators.immediate_dominator(dead_block), None);
+    assert!(!dominators.dominates(BasicBlock::from_ptr(entry_block), dead_block));
+
+    let loops = Loop::find_loops(&cfg, &dominators);
+    assert_eq!(loops.len(), 2);
+    let (outer, inner) = (&loops[0], &loops[1]);
+    assert!(outer.contains(inner.header()));
+    assert!(!inner.contains(outer.header()));
+    assert!(outer.blocks().len() > inner.blocks().len());
+    for loop_ in loops.iter() {
+        assert_eq!(loop_.latches().len(), 1);
+        assert!(dominators.dominates(loop_.header(), loop_.latches()[0]));
+        assert!(loop_.header().get_name().starts_with("for.cond"));
+    }
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate llvm_sys;
+
+use llvm_sys_wrapper::*;
+use llvm_sys::execution_engine::LLVMCreateGenericValueOfInt;
+use std::ffi::CString;
+
+#[test]
+fn test_array_type() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("array_type");
+
+    // [4 x Int32] table = [10, 20, 30, 40]
+    let table_type = context.ArrayType(context.Int32Type(), 4);
+    let mut values = [context.SInt32(10), context.SInt32(20), context.SInt32(30), context.SInt32(40)];
+    let table_value = context.ConstArray(context.Int32Type(), &mut values);
+    let table_name = CString::new("table").unwrap();
+    let table = unsafe { LLVMAddGlobal(module.as_ref(), table_type, table_name.as_ptr()) };
+    unsafe { LLVMSetInitializer(table, table_value) };
+
+    // [6 x Int8] message = "hello\0"
+    let message_value = context.ConstString(b"hello", true);
+    assert_eq!(unsafe { LLVMGetArrayLength(LLVMTypeOf(message_value)) }, 6);
+    let raw_value = context.ConstString(b"hello", false);
+    assert_eq!(unsafe { LLVMGetArrayLength(LLVMTypeOf(raw_value)) }, 5);
+
+    // Int32 get(Int32 index)
+    let fun_type = fn_type_in_context!(context; context.Int32Type(), context.Int32Type());
+    assert_eq!(fun_type, context.FunctionType(context.Int32Type(), &mut [context.Int32Type()], false));
+    let function = module.add_function("get", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let mut indices = [context.SInt32(0), function.get_param(0)];
+    let ptr = builder.build_inbounds_gep(table, &mut indices);
+    builder.build_ret(builder.build_load(ptr));
+
+    // variadic function type
+    let printf_type = fn_type_in_context!(context; context.Int32Type(), context.CharPointerType() ,,,);
+    assert_eq!(unsafe { LLVMIsFunctionVarArg(printf_type) }, 1);
+
+    // verify & interpret
+    match module.verify() {
+        Ok(_) => {
+            let interpreter = module.create_interpreter().unwrap();
+            let mut args = [unsafe { LLVMCreateGenericValueOfInt(context.Int32Type(), 2, 1) }];
+            let run_result = interpreter.run_function(function.as_ref(), &mut args);
+            assert_eq!(run_result.to_int(), 30);
+        },
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::env;
+use std::fs;
+use std::io;
+
+#[test]
+fn test_cfg_dot() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("cfg_dot");
+
+    // Int32 pick(Int32 x)
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let function = module.add_function("pick", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    let positive_block = function.append_basic_block("positive");
+    let negative_block = function.append_basic_block("negative");
+    let one_block = function.append_basic_block("one");
+    builder.position_at_end(entry_block);
+
+    let x = function.get_param(0);
+    let cond = builder.build_icmp_sgt_with_name(x, context.SInt32(0), "is_positive");
+    builder.build_cond_br(cond, positive_block, negative_block);
+
+    builder.position_at_end(positive_block);
+    builder.build_switch(x, negative_block, &[(context.SInt32(1), one_block)]);
+
+    builder.position_at_end(negative_block);
+    builder.build_ret(context.SInt32(0));
+
+    builder.position_at_end(one_block);
+    builder.build_ret(context.SInt32(1));
+
+    // declarations have no cfg
+    let decl_type = fn_type!(context.VoidType());
+    module.add_function("external", decl_type);
+
+    let dot = function.to_dot();
+    assert!(dot.starts_with("digraph \"CFG for 'pick' function\" {"));
+    assert!(dot.contains("block0 [label=\"{entry:\\l%is_positive = icmp sgt i32 %0, 0\\l"));
+    assert!(dot.contains("block0 -> block1 [label=\"%is_positive: true\"];"));
+    assert!(dot.contains("block0 -> block2 [label=\"%is_positive: false\"];"));
+    assert!(dot.contains("block1 -> block2 [label=\"default\"];"));
+    assert!(dot.contains("block1 -> block3 [label=\"i32 1\"];"));
+    assert!(dot.trim_end().ends_with("}"));
+
+    let dir = env::temp_dir().join("llvm_sys_wrapper_cfg_dot");
+    fs::create_dir_all(&dir).unwrap();
+    module.write_cfg_dots(&dir).unwrap();
+    assert_eq!(fs::read_to_string(dir.join("cfg.pick.dot")).unwrap(), dot);
+    assert!(!dir.join("cfg.external.dot").exists());
+
+    match module.write_cfg_dots("/nonexistent-directory") {
+        Err(Error::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::NotFound),
+        _ => panic!("writing to a missing directory succeeded")
+    }
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_const_expr_initializers() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+    let module = context.create_module("const_expr");
+
+    // @numbers = constant [4 x i32] [1, 2, 3, 4]
+    let mut values = [context.SInt32(1), context.SInt32(2), context.SInt32(3), context.SInt32(4)];
+    let numbers = module.add_global_with_initializer("numbers", context.ConstArray(context.Int32Type(), &mut values), true);
+    assert_eq!(module.get_named_global("numbers"), Some(numbers));
+
+    // @third = i32* &numbers[2]
+    let third = ConstExpr::inbounds_gep(numbers, &mut [context.SInt32(0), context.SInt32(2)]);
+    module.add_global_with_initializer("third", third, false);
+
+    // @numbers_size = i64 (&numbers[4] - &numbers[0])
+    let end = ConstExpr::gep(numbers, &mut [context.SInt32(0), context.SInt32(4)]);
+    let size = ConstExpr::sub(ConstExpr::ptr_to_int(end, context.Int64Type()), ConstExpr::ptr_to_int(numbers, context.Int64Type()));
+    module.add_global_with_initializer("numbers_size", size, true);
+
+    // @third_aligned = i32 zext ((ptrtoint @third) % 4 == 0)
+    let aligned = ConstExpr::icmp_eq(ConstExpr::urem(ConstExpr::ptr_to_int(third, context.Int64Type()), context.UInt64(4)), context.UInt64(0));
+    module.add_global_with_initializer("third_aligned", ConstExpr::zext(aligned, context.Int32Type()), true);
+
+    // @bytes = i8* bitcast @numbers, @first_byte = i8 load through a cast pointer
+    let bytes = ConstExpr::pointer_cast(numbers, context.Int8PointerType());
+    module.add_global_with_initializer("bytes", bytes, true);
+
+    // @second_field = i32* &pair.1 of a struct global
+    let mut fields = [context.SInt32(7), context.SInt64(8)];
+    let pair = unsafe { LLVMConstStructInContext(context.as_ref(), fields.as_mut_ptr(), 2, 0) };
+    let pair_global = module.add_global_with_initializer("pair", pair, true);
+    module.add_global_with_initializer("second_field", ConstExpr::struct_gep(pair_global, 1), true);
+
+    // constants folded at construction
+    let picked = ConstExpr::select(ConstExpr::icmp_slt(context.SInt32(3), context.SInt32(5)), context.SInt32(10), context.SInt32(20));
+    assert_eq!(Value::from_ptr(picked).const_sext_value(), Some(10));
+    assert_eq!(Value::from_ptr(ConstExpr::extract_value(pair, 1)).const_sext_value(), Some(8));
+    assert_eq!(Value::from_ptr(ConstExpr::fp_to_si(ConstExpr::fmul(context.Double(2.5), context.Double(4.0)), context.Int32Type())).const_sext_value(), Some(10));
+    assert_eq!(Value::from_ptr(ConstExpr::neg(context.SInt32(5))).const_sext_value(), Some(-5));
+    assert_eq!(Value::from_ptr(ConstExpr::shl(context.UInt32(1), context.UInt32(4))).const_zext_value(), Some(16));
+    let inserted = ConstExpr::insert_value(pair, context.SInt32(9), 0);
+    assert_eq!(Value::from_ptr(ConstExpr::extract_value(inserted, 0)).const_sext_value(), Some(9));
+    module.add_global_with_initializer("picked", picked, true);
+
+    assert_eq!(module.get_globals().len(), 8);
+    assert!(module.get_named_global("missing").is_none());
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    // initializers evaluated by the JIT
+    let engine = module.create_jit_engine().unwrap();
+    let address = |name: &str| engine.get_global_value_address(name).unwrap() as usize;
+    unsafe {
+        let numbers_address = address("numbers");
+        assert_eq!(*(address("third") as *const usize), numbers_address + 8);
+        assert_eq!(**(address("third") as *const *const i32), 3);
+        assert_eq!(*(address("numbers_size") as *const i64), 16);
+        assert_eq!(*(address("third_aligned") as *const i32), 1);
+        assert_eq!(*(address("bytes") as *const usize), numbers_address);
+        assert_eq!(**(address("second_field") as *const *const i64), 8);
+        assert_eq!(*(address("picked") as *const i32), 10);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,87 @@
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_integer_constants() {
+    // create context
+    let context = Context::global_context();
+
+    // signed constants take signed values
+    let minus_one = Value::from_ptr(context.SInt32(-1));
+    assert_eq!(minus_one.const_sext_value(), Some(-1));
+    assert_eq!(minus_one.const_zext_value(), Some(0xffff_ffff));
+    assert_eq!(Value::from_ptr(context.SInt8(-128)).const_sext_value(), Some(-128));
+    assert_eq!(Value::from_ptr(context.SInt(40, -2)).const_sext_value(), Some(-2));
+    assert_eq!(Value::from_ptr(LLVM::Const::SInt64(i64::min_value())).const_sext_value(), Some(i64::min_value()));
+
+    // full 128-bit range
+    let big = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+    let value = Value::from_ptr(context.UInt128(big));
+    assert_eq!(value.const_u128_value(), Some(big));
+    assert_eq!(value.const_zext_value(), None);
+    assert_eq!(value.const_words(), Some(vec![0xfedc_ba98_7654_3210, 0x0123_4567_89ab_cdef]));
+    assert_eq!(Value::from_ptr(context.SInt128(i128::min_value())).const_i128_value(), Some(i128::min_value()));
+    assert_eq!(Value::from_ptr(context.SInt128(-5)).const_i128_value(), Some(-5));
+    assert_eq!(Value::from_ptr(LLVM::Const::UInt128(u128::max_value())).const_u128_value(), Some(u128::max_value()));
+    assert_eq!(Value::from_ptr(context.SInt16(-3)).const_i128_value(), Some(-3));
+
+    // arbitrary precision words, least significant first
+    let words = [1u64, 2, 3];
+    let wide = Value::from_ptr(context.IntOfArbitraryPrecision(192, &words).unwrap());
+    assert_eq!(wide.const_words(), Some(words.to_vec()));
+    assert_eq!(wide.const_u128_value(), None);
+    assert!(wide.print_to_string().starts_with("i192 "));
+    assert_eq!(Value::from_ptr(LLVM::Const::IntOfArbitraryPrecision(8, &[0x1ff]).unwrap()).const_zext_value(), Some(0xff));
+    assert!(context.IntOfArbitraryPrecision(0, &words).is_err());
+    assert!(context.IntOfArbitraryPrecision(1 << 24, &words).is_err());
+    assert!(LLVM::Const::IntOfArbitraryPrecision(0, &[]).is_err());
+
+    // decimal and hexadecimal strings
+    assert_eq!(Value::from_ptr(context.IntOfString(64, "-42", 10).unwrap()).const_sext_value(), Some(-42));
+    assert_eq!(Value::from_ptr(context.IntOfString(32, "ff", 16).unwrap()).const_zext_value(), Some(255));
+    let from_string = Value::from_ptr(context.IntOfString(128, "170141183460469231731687303715884105727", 10).unwrap());
+    assert_eq!(from_string.const_i128_value(), Some(i128::max_value()));
+    assert_eq!(Value::from_ptr(LLVM::Const::IntOfString(16, "7fff", 16).unwrap()).const_sext_value(), Some(0x7fff));
+
+    // text LLVM can not parse is rejected before it reaches LLVM
+    assert!(context.IntOfString(32, "", 10).is_err());
+    assert!(context.IntOfString(32, "-", 10).is_err());
+    assert!(context.IntOfString(32, "hello", 10).is_err());
+    assert!(context.IntOfString(32, "12", 7).is_err());
+    assert!(context.IntOfString(32, "102", 2).is_err());
+    assert!(context.IntOfString(8, "123456789", 16).is_err());
+    assert!(context.IntOfString(0, "1", 10).is_err());
+    assert!(LLVM::Const::IntOfString(32, "1.5", 10).is_err());
+    assert_eq!(Value::from_ptr(context.IntOfString(32, "Zz", 36).unwrap()).const_zext_value(), Some(35 * 36 + 35));
+
+    // non integer constants have no integer value
+    assert_eq!(Value::from_ptr(context.Double(1.5)).const_zext_value(), None);
+    assert_eq!(Value::from_ptr(context.Double(1.5)).const_u128_value(), None);
+}
+
+#[test]
+fn test_real_constants() {
+    // create context
+    let context = Context::global_context();
+
+    assert_eq!(Value::from_ptr(context.Double(0.25)).const_real_value(), Some(0.25));
+    assert_eq!(Value::from_ptr(context.RealOfString(context.DoubleType(), "3.5").unwrap()).const_real_value(), Some(3.5));
+    assert_eq!(Value::from_ptr(context.RealOfString(context.FloatType(), "-0.125").unwrap()).const_real_value(), Some(-0.125));
+    assert_eq!(Value::from_ptr(LLVM::Const::RealOfString(LLVM::Type::Double(), "1e3").unwrap()).const_real_value(), Some(1000.0));
+    assert_eq!(Value::from_ptr(context.SInt32(1)).const_real_value(), None);
+    assert_eq!(Value::from_ptr(context.RealOfString(context.DoubleType(), "0x1.8p1").unwrap()).const_real_value(), Some(3.0));
+    assert_eq!(Value::from_ptr(context.RealOfString(context.DoubleType(), "-inf").unwrap()).const_real_value(), Some(-std::f64::INFINITY));
+
+    // text LLVM can not parse is rejected before it reaches LLVM
+    for text in ["", "-", ".", "hello", "1.2.3", "1e", "1e+", "0x1.8", "0x", "1,5"].iter() {
+        assert!(context.RealOfString(context.DoubleType(), text).is_err(), "{:?}", text);
+    }
+    assert!(context.RealOfString(context.Int32Type(), "1.5").is_err());
+    assert!(LLVM::Const::RealOfString(LLVM::Type::Double(), "1.2.3").is_err());
+
+    // wider than double
+    let quad = Value::from_ptr(context.RealOfString(context.FP128Type(), "1.000000000000000000000000000001").unwrap());
+    assert!(quad.is_const_real());
+    assert_eq!(quad.const_real_value(), Some(1.0));
+}
\ No newline at end of file
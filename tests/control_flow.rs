@@ -0,0 +1,177 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate llvm_sys;
+
+use llvm_sys_wrapper::*;
+use llvm_sys::execution_engine::LLVMCreateGenericValueOfInt;
+
+#[test]
+fn test_control_flow() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("control_flow");
+
+    //
+    // Int32 max(Int32, Int32) using if/else
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type(), context.Int32Type());
+    let max_func = module.add_function("max", fun_type);
+    let entry_block = max_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let a = max_func.get_param(0);
+    let b = max_func.get_param(1);
+    let cond = builder.build_icmp_sgt(a, b);
+    let max = builder.build_if(cond, |_| Some(a), |_| Some(b));
+    builder.build_ret(max.unwrap());
+
+    //
+    // Int32 abs(Int32) using if with an early return
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let abs_func = module.add_function("abs_value", fun_type);
+    let entry_block = abs_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let x = abs_func.get_param(0);
+    let cond = builder.build_icmp_slt(x, context.SInt32(0));
+    let merged = builder.build_if(cond, |b| {
+        b.build_ret(b.build_neg(x));
+        None
+    }, |_| None);
+    assert!(merged.is_none());
+    builder.build_ret(x);
+
+    //
+    // Int32 sum_while(Int32 n) = 0 + 1 + ... + (n - 1)
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let sum_while_func = module.add_function("sum_while", fun_type);
+    let entry_block = sum_while_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let n = sum_while_func.get_param(0);
+    let i = builder.build_alloca(context.Int32Type());
+    let acc = builder.build_alloca(context.Int32Type());
+    builder.build_store(context.SInt32(0), i);
+    builder.build_store(context.SInt32(0), acc);
+    builder.build_while(|b| {
+        b.build_icmp_slt(b.build_load(i), n)
+    }, |b| {
+        let i_val = b.build_load(i);
+        b.build_store(b.build_add(b.build_load(acc), i_val), acc);
+        b.build_store(b.build_add(i_val, context.SInt32(1)), i);
+    });
+    builder.build_ret(builder.build_load(acc));
+
+    //
+    // Int32 sum_for(Int32 n) = 0 + 1 + ... + (n - 1)
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let sum_for_func = module.add_function("sum_for", fun_type);
+    let entry_block = sum_for_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let n = sum_for_func.get_param(0);
+    let acc = builder.build_alloca(context.Int32Type());
+    builder.build_store(context.SInt32(0), acc);
+    builder.build_for(context.SInt32(0), n, context.SInt32(1), |b, index| {
+        b.build_store(b.build_add(b.build_load(acc), index), acc);
+    });
+    builder.build_ret(builder.build_load(acc));
+
+    //
+    // Int32 classify(Int32) using switch
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let classify_func = module.add_function("classify", fun_type);
+    let entry_block = classify_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let x = classify_func.get_param(0);
+    let mut cases: Vec<(LLVMValueRef, Box<dyn Fn(&Builder) -> Option<LLVMValueRef> + '_>)> = Vec::new();
+    cases.push((context.SInt32(1), Box::new(|_: &Builder| Some(context.SInt32(10)))));
+    cases.push((context.SInt32(2), Box::new(|_: &Builder| Some(context.SInt32(20)))));
+    let result = builder.build_switch_with(x, cases, |b| Some(b.build_mul(x, context.SInt32(100))));
+    builder.build_ret(result.unwrap());
+
+    //
+    // Int32 sign(Int32) using if where both branches return
+    //
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let sign_func = module.add_function("sign", fun_type);
+    let entry_block = sign_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let x = sign_func.get_param(0);
+    let cond = builder.build_icmp_slt(x, context.SInt32(0));
+    let merged = builder.build_if(cond, |b| {
+        b.build_ret(context.SInt32(-1));
+        None
+    }, |b| {
+        b.build_ret(context.SInt32(1));
+        None
+    });
+    assert!(merged.is_none());
+    // the merge block is unreachable, but the caller can still terminate it
+    builder.build_ret(builder.build_add(x, context.SInt32(1)));
+
+    // verify & interpret
+    match module.verify() {
+        Ok(_) => {
+            let engine = module.create_interpreter().unwrap();
+            let int32 = |n: i64| unsafe { LLVMCreateGenericValueOfInt(context.Int32Type(), n as u64, 1) };
+
+            let mut args = [int32(3), int32(7)];
+            assert_eq!(engine.run_function(max_func.as_ref(), &mut args).to_int(), 7);
+            let mut args = [int32(9), int32(2)];
+            assert_eq!(engine.run_function(max_func.as_ref(), &mut args).to_int(), 9);
+
+            let mut args = [int32(-5)];
+            assert_eq!(engine.run_function(abs_func.as_ref(), &mut args).to_int() as i32, 5);
+            let mut args = [int32(6)];
+            assert_eq!(engine.run_function(abs_func.as_ref(), &mut args).to_int() as i32, 6);
+
+            let mut args = [int32(10)];
+            assert_eq!(engine.run_function(sum_while_func.as_ref(), &mut args).to_int(), 45);
+            let mut args = [int32(10)];
+            assert_eq!(engine.run_function(sum_for_func.as_ref(), &mut args).to_int(), 45);
+
+            let mut args = [int32(1)];
+            assert_eq!(engine.run_function(classify_func.as_ref(), &mut args).to_int(), 10);
+            let mut args = [int32(2)];
+            assert_eq!(engine.run_function(classify_func.as_ref(), &mut args).to_int(), 20);
+            let mut args = [int32(3)];
+            assert_eq!(engine.run_function(classify_func.as_ref(), &mut args).to_int(), 300);
+
+            let mut args = [int32(-4)];
+            assert_eq!(engine.run_function(sign_func.as_ref(), &mut args).to_int() as i32, -1);
+            let mut args = [int32(4)];
+            assert_eq!(engine.run_function(sign_func.as_ref(), &mut args).to_int() as i32, 1);
+        },
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
+
+#[test]
+#[should_panic(expected = "some branches yield a value and others do not.")]
+fn test_if_with_mismatched_branches() {
+    let context = Context::new();
+    let builder = context.create_builder();
+    let module = context.create_module("mismatched_branches");
+
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let func = module.add_function("mismatched", fun_type);
+    let entry_block = func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let x = func.get_param(0);
+    let cond = builder.build_icmp_slt(x, context.SInt32(0));
+    builder.build_if(cond, |_| Some(x), |_| None);
+}
\ No newline at end of file
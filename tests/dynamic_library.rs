@@ -0,0 +1,86 @@
+#![cfg(unix)]
+
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::env;
+use std::fs;
+use std::mem;
+use std::process::{self, Command};
+
+const LIBRARY_SOURCE: &str = "
+int ext_counter = 5;
+
+int ext_mul_add(int a, int b) {
+    return a * b + ext_counter;
+}
+";
+
+#[cfg(target_os = "macos")]
+const LIBRARY_NAME: &str = "libext.dylib";
+#[cfg(not(target_os = "macos"))]
+const LIBRARY_NAME: &str = "libext.so";
+
+#[test]
+fn test_load_library() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // build a shared library with the system C compiler
+    let dir = env::temp_dir().join(format!("llvm_sys_wrapper_dylib_{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("ext.c");
+    let library = dir.join(LIBRARY_NAME);
+    fs::write(&source, LIBRARY_SOURCE).unwrap();
+    let compiler = env::var("CC").unwrap_or("cc".to_string());
+    let status = match Command::new(&compiler).arg("-shared").arg("-fPIC").arg("-o").arg(&library).arg(&source).status() {
+        Ok(status) => status,
+        Err(err) => {
+            eprintln!("skipping test_load_library: could not run C compiler '{}': {}", compiler, err);
+            fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+    };
+    assert!(status.success());
+
+    assert!(LLVM::search_for_address_of_symbol("ext_mul_add").is_none());
+    assert!(LLVM::load_library_permanently("/nonexistent-directory/libnothing.so").is_err());
+    LLVM::load_library_permanently(library.to_str().unwrap()).unwrap();
+    let counter_address = LLVM::search_for_address_of_symbol("ext_counter").unwrap();
+    assert_eq!(unsafe { *(counter_address as *const i32) }, 5);
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("dynamic_library");
+
+    // Int32 call_ext(Int32 x) { ret ext_mul_add(x, x) }
+    let ext_type = fn_type!(context.Int32Type(), context.Int32Type(), context.Int32Type());
+    let ext_func = module.add_function("ext_mul_add", ext_type);
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let function = module.add_function("call_ext", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let x = function.get_param(0);
+    let mut args = [x, x];
+    builder.build_ret(builder.build_call(ext_func.as_ref(), &mut args));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    let call_ext: extern "C" fn(i32) -> i32 = unsafe { mem::transmute(engine.get_function_address("call_ext").unwrap() as usize) };
+    assert_eq!(call_ext(6), 41);
+
+    // lookups through the engine fall back to the loaded library
+    let ext_mul_add: extern "C" fn(i32, i32) -> i32 = unsafe { mem::transmute(engine.get_symbol_address("ext_mul_add").unwrap() as usize) };
+    assert_eq!(ext_mul_add(2, 3), 11);
+    assert!(engine.get_symbol_address("no_such_symbol_anywhere").is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::mem;
+
+#[test]
+fn test_engine_modules() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+    let builder = context.create_builder();
+
+    // first module: Int32 main(Int32 argc, Int8** argv, Int8** envp) { ret argc }
+    let module = context.create_module("engine_main");
+    let char_ptr_ptr = context.PointerType(context.CharPointerType());
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type(), char_ptr_ptr, char_ptr_ptr);
+    let main_func = module.add_function("main", fun_type);
+    let entry_block = main_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret(main_func.get_param(0));
+
+    // second module: Int32 counter with constructor and destructor
+    let ctor_module = context.create_module("engine_ctors");
+    let counter = unsafe { LLVMAddGlobal(ctor_module.as_ref(), context.Int32Type(), b"counter\0".as_ptr() as *const _) };
+    unsafe { LLVMSetInitializer(counter, context.SInt32(0)) };
+
+    let void_type = fn_type!(context.VoidType());
+    let init_func = ctor_module.add_function("init", void_type);
+    builder.position_at_end(init_func.append_basic_block("entry"));
+    builder.build_store(context.SInt32(42), counter);
+    builder.build_ret_void();
+
+    let fini_func = ctor_module.add_function("fini", void_type);
+    builder.position_at_end(fini_func.append_basic_block("entry"));
+    builder.build_store(context.SInt32(7), counter);
+    builder.build_ret_void();
+
+    // { i32, void ()*, i8* } entries of llvm.global_ctors / llvm.global_dtors
+    let entry_type = context.StructType(&mut [context.Int32Type(), context.PointerType(void_type), context.CharPointerType()], false).as_ref();
+    for &(name, function) in [("llvm.global_ctors\0", &init_func), ("llvm.global_dtors\0", &fini_func)].iter() {
+        let mut fields = [context.SInt32(65535), function.as_ref(), context.PointerNull(context.CharPointerType())];
+        let mut entries = [unsafe { LLVMConstStructInContext(context.as_ref(), fields.as_mut_ptr(), 3, 0) }];
+        let array = context.ConstArray(entry_type, &mut entries);
+        let global = unsafe { LLVMAddGlobal(ctor_module.as_ref(), LLVMTypeOf(array), name.as_ptr() as *const _) };
+        unsafe {
+            LLVMSetInitializer(global, array);
+            LLVMSetLinkage(global, LLVMLinkage::LLVMAppendingLinkage);
+        }
+    }
+
+    match (module.verify(), ctor_module.verify()) {
+        (Ok(_), Ok(_)) => {},
+        (Err(msg), _) | (_, Err(msg)) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    let ctor_module_ref = ctor_module.as_ref();
+    let ctor_handle = engine.add_module(ctor_module);
+    assert_eq!(ctor_handle.as_ref(), ctor_module_ref);
+
+    // look up across modules
+    assert!(engine.find_function("init").is_some());
+    assert!(engine.find_function("no_such_function").is_none());
+    let main_func = engine.find_function("main").unwrap();
+
+    engine.run_static_constructors();
+    let counter_address = engine.get_global_value_address("counter").unwrap();
+    let counter_ptr = counter_address as usize as *const i32;
+    assert_eq!(unsafe { *counter_ptr }, 42);
+
+    let init_address = engine.get_function_address("init").unwrap();
+    let init: extern "C" fn() = unsafe { mem::transmute(init_address as usize) };
+    unsafe { *(counter_ptr as *mut i32) = 0 };
+    init();
+    assert_eq!(unsafe { *counter_ptr }, 42);
+
+    engine.run_static_destructors();
+    assert_eq!(unsafe { *counter_ptr }, 7);
+
+    assert_eq!(engine.run_function_as_main(main_func.as_ref(), &["program", "a", "b"], &["HOME=/tmp"]).unwrap(), 3);
+    assert!(engine.run_function_as_main(main_func.as_ref(), &["program", "a\0b"], &[]).is_err());
+    assert!(engine.run_function_as_main(main_func.as_ref(), &["program"], &["HOME=\0"]).is_err());
+
+    // the removed module is owned by the caller again
+    let removed = engine.remove_module(ctor_handle).unwrap();
+    assert_eq!(removed.as_ref(), ctor_module_ref);
+    assert!(engine.find_function("init").is_none());
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_verify_error() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("broken");
+
+    // Int32 broken() { ret void }
+    let fun_type = fn_type!(context.Int32Type());
+    let function = module.add_function("broken", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret_void();
+
+    match module.verify() {
+        Ok(_) => panic!("broken module passed verification"),
+        Err(Error::Verify(err)) => assert!(err.to_string().contains("Function return type does not match operand type of return inst!")),
+        Err(err) => panic!("unexpected error: {}", err)
+    }
+
+    // usable as a boxed std error
+    let boxed: Box<dyn std::error::Error> = Box::new(module.verify().unwrap_err());
+    assert!(boxed.to_string().starts_with("verification failed"));
+}
+
+#[test]
+fn test_parse_and_print() {
+    let context = Context::new();
+
+    let module = Module::parse_ir(context.as_ref(), "define i32 @seven() {\n  ret i32 7\n}\n").unwrap();
+    assert!(module.verify().is_ok());
+    assert!(module.print_module_to_string().contains("define i32 @seven()"));
+
+    match Module::parse_ir(context.as_ref(), "define i32 @oops( {") {
+        Err(Error::Parse(msg)) => assert!(!msg.is_empty()),
+        _ => panic!("invalid IR was parsed")
+    }
+
+    match module.print_module_to_file("/nonexistent-directory/seven.ll") {
+        Err(err) => {
+            match err {
+                Error::Io(ref io_err) => assert!(!io_err.to_string().is_empty()),
+                _ => panic!("unexpected error: {}", err)
+            }
+            // the io::Error is kept as the source
+            assert!(std::error::Error::source(&err).is_some());
+        },
+        _ => panic!("printing to a missing directory succeeded")
+    }
+}
\ No newline at end of file
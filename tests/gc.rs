@@ -0,0 +1,90 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate libc;
+
+use llvm_sys_wrapper::*;
+use libc::c_void;
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ROOT_CHAIN: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static FOUND_ROOT: AtomicUsize = AtomicUsize::new(0);
+static FOUND_FRAMES: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn gc_allocate() -> *mut c_void {
+    let object = Box::into_raw(Box::new([0usize; 2])) as *mut c_void;
+    ALLOCATED.store(object as usize, Ordering::SeqCst);
+    object
+}
+
+// a precise collector would trace from the roots found here
+extern "C" fn gc_collect() {
+    let stack = unsafe { ShadowStack::from_address(ROOT_CHAIN.load(Ordering::SeqCst) as u64) };
+    FOUND_FRAMES.store(stack.frames_count(), Ordering::SeqCst);
+    stack.visit_roots(|root, metadata| {
+        assert!(metadata.is_null());
+        FOUND_ROOT.store(unsafe { *root } as usize, Ordering::SeqCst);
+    });
+}
+
+#[test]
+fn test_shadow_stack() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("gc");
+
+    let i8_ptr = context.Int8PointerType();
+    let allocate_func = module.add_function("gc_allocate", fn_type!(i8_ptr));
+    let collect_func = module.add_function("gc_collect", fn_type!(context.VoidType()));
+
+    // Int8* run() with one stack root
+    let run_func = module.add_function("run", fn_type!(i8_ptr));
+    run_func.set_gc("shadow-stack");
+    assert_eq!(run_func.gc(), Some("shadow-stack".to_string()));
+    assert_eq!(allocate_func.gc(), None);
+    let entry_block = run_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let root = builder.build_alloca(i8_ptr);
+    builder.build_gcroot(root, context.PointerNull(i8_ptr));
+    let object = builder.build_call(allocate_func.as_ref(), &mut []);
+    builder.build_store(object, root);
+    builder.build_call(collect_func.as_ref(), &mut []);
+
+    // object[0] = object, through the write and read barriers
+    let object = builder.build_load(root);
+    let field = builder.build_bitcast(object, context.PointerType(i8_ptr));
+    builder.build_gcwrite(object, object, field);
+    builder.build_ret(builder.build_gcread(object, field));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    engine.add_host_function(&allocate_func, gc_allocate as extern "C" fn() -> *mut c_void).unwrap();
+    engine.add_host_function(&collect_func, gc_collect as extern "C" fn()).unwrap();
+
+    let run: extern "C" fn() -> *mut c_void = unsafe { mem::transmute(engine.get_function_address("run").unwrap() as usize) };
+    ROOT_CHAIN.store(engine.get_global_value_address("llvm_gc_root_chain").unwrap() as usize, Ordering::SeqCst);
+
+    let result = run();
+    let allocated = ALLOCATED.load(Ordering::SeqCst);
+    assert_eq!(result as usize, allocated);
+    assert_eq!(FOUND_FRAMES.load(Ordering::SeqCst), 1);
+    assert_eq!(FOUND_ROOT.load(Ordering::SeqCst), allocated);
+    assert_eq!(unsafe { *(allocated as *const usize) }, allocated);
+
+    // the frame is popped after returning
+    let stack = unsafe { ShadowStack::from_address(ROOT_CHAIN.load(Ordering::SeqCst) as u64) };
+    assert_eq!(stack.frames_count(), 0);
+    unsafe { drop(Box::from_raw(allocated as *mut [usize; 2])) };
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate libc;
+
+use llvm_sys_wrapper::*;
+use libc::c_void;
+use std::mem;
+use std::sync::atomic::{AtomicIsize, Ordering};
+
+static LOGGED: AtomicIsize = AtomicIsize::new(0);
+
+extern "C" fn host_log(value: i32) {
+    LOGGED.store(value as isize, Ordering::SeqCst);
+}
+
+extern "C" fn zero() -> i32 {
+    0
+}
+
+// calls the Rust closure passed to JIT code as an opaque environment pointer
+extern "C" fn invoke_closure(env: *mut c_void, value: i32) -> i32 {
+    let closure = unsafe { &mut *(env as *mut &mut dyn FnMut(i32) -> i32) };
+    closure(value)
+}
+
+#[test]
+fn test_host_functions() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("host_function");
+
+    // declared host functions
+    let log_type = fn_type!(context.VoidType(), context.Int32Type());
+    let log_func = module.add_function("host_log", log_type);
+    let invoke_type = fn_type!(context.Int32Type(), context.Int8PointerType(), context.Int32Type());
+    let invoke_func = module.add_function("invoke_closure", invoke_type);
+
+    // Int32 apply(Int8* env, Int32 x) { host_log(x); return invoke_closure(env, x) + 1 }
+    let fun_type = fn_type!(context.Int32Type(), context.Int8PointerType(), context.Int32Type());
+    let apply_func = module.add_function("apply", fun_type);
+    let entry_block = apply_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let mut args = [apply_func.get_param(1)];
+    builder.build_call(log_func.as_ref(), &mut args);
+    let mut args = [apply_func.get_param(0), apply_func.get_param(1)];
+    let result = builder.build_call(invoke_func.as_ref(), &mut args);
+    builder.build_ret(builder.build_add(result, context.SInt32(1)));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+
+    // signature mismatch is rejected
+    assert!(engine.add_host_function(&invoke_func, zero as extern "C" fn() -> i32).is_err());
+
+    // process wide symbol and per engine mapping
+    LLVM::add_host_function("host_log", host_log as extern "C" fn(i32));
+    engine.add_host_function(&invoke_func, invoke_closure as extern "C" fn(*mut c_void, i32) -> i32).unwrap();
+
+    let mut calls = 0;
+    let apply: extern "C" fn(*mut c_void, i32) -> i32 = unsafe { mem::transmute(engine.get_function_address("apply").unwrap() as usize) };
+    {
+        let mut closure = |x: i32| { calls += 1; x * 10 };
+        let mut closure_ref: &mut dyn FnMut(i32) -> i32 = &mut closure;
+        let env = &mut closure_ref as *mut &mut dyn FnMut(i32) -> i32 as *mut c_void;
+        assert_eq!(apply(env, 4), 41);
+        assert_eq!(apply(env, 5), 51);
+    }
+    assert_eq!(calls, 2);
+    assert_eq!(LOGGED.load(Ordering::SeqCst), 5);
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+#![cfg(target_arch = "x86_64")]
+
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::mem;
+
+#[test]
+fn test_inline_asm() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("inline_asm");
+
+    // Int64 timestamp() using rdtsc, with a pause hint before it
+    let fun_type = fn_type!(context.Int64Type());
+    let timestamp_func = module.add_function("timestamp", fun_type);
+    let entry_block = timestamp_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let pause = builder.build_inline_asm(fn_type!(context.VoidType()), "pause", "", true, false, AsmDialect::ATT).unwrap();
+    builder.build_call(pause, &mut []);
+    let rdtsc = builder.build_inline_asm(fun_type, "rdtsc\nshlq $$32, %rdx\norq %rdx, %rax", "={rax},~{rdx}", true, false, AsmDialect::ATT).unwrap();
+    builder.build_ret(builder.build_call(rdtsc, &mut []));
+
+    // Int32 intel_add(Int32 x) using Intel syntax with a fixed register
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let intel_func = module.add_function("intel_add", fun_type);
+    let entry_block = intel_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let add = builder.build_inline_asm(fun_type, "add eax, 100", "={eax},{eax}", false, false, AsmDialect::Intel).unwrap();
+    let mut args = [intel_func.get_param(0)];
+    builder.build_ret(builder.build_call(add, &mut args));
+
+    // module level assembly defining Int32 asm_forty_two() and Int32 asm_seven()
+    module.set_inline_asm(".text\n.globl asm_forty_two\nasm_forty_two:\n  movl $42, %eax\n  ret");
+    module.append_inline_asm(".globl asm_seven\nasm_seven:\n  movl $7, %eax\n  ret");
+    let ir = module.print_module_to_string();
+    assert!(ir.contains("module asm \".text\"\nmodule asm \".globl asm_forty_two\"\n"));
+    assert!(ir.contains("module asm \"  ret\"\nmodule asm \".globl asm_seven\"\n"));
+
+    let fun_type = fn_type!(context.Int32Type());
+    let forty_two_func = module.add_function("asm_forty_two", fun_type);
+    let seven_func = module.add_function("asm_seven", fun_type);
+    let sum_func = module.add_function("asm_sum", fun_type);
+    let entry_block = sum_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let forty_two = builder.build_call(forty_two_func.as_ref(), &mut []);
+    let seven = builder.build_call(seven_func.as_ref(), &mut []);
+    builder.build_ret(builder.build_add(forty_two, seven));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    let timestamp: extern "C" fn() -> u64 = unsafe { mem::transmute(engine.get_function_address("timestamp").unwrap() as usize) };
+    let first = timestamp();
+    let second = timestamp();
+    assert!(first > 0);
+    assert!(second >= first);
+
+    let intel_add: extern "C" fn(i32) -> i32 = unsafe { mem::transmute(engine.get_function_address("intel_add").unwrap() as usize) };
+    assert_eq!(intel_add(23), 123);
+
+    let asm_sum: extern "C" fn() -> i32 = unsafe { mem::transmute(engine.get_function_address("asm_sum").unwrap() as usize) };
+    assert_eq!(asm_sum(), 49);
+}
+
+#[test]
+fn test_intel_asm_with_operands() {
+    let context = Context::new();
+    let builder = context.create_builder();
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    assert!(builder.build_inline_asm(fun_type, "lea $0, [$1 + 1]", "=r,r", false, false, AsmDialect::Intel).is_err());
+    assert!(builder.build_inline_asm(fun_type, "lea ${0:k}, [eax + 1]", "=r", false, false, AsmDialect::Intel).is_err());
+    // '$$' is a literal '$'
+    assert!(builder.build_inline_asm(fun_type, "mov eax, $$1", "={eax}", false, false, AsmDialect::Intel).is_ok());
+}
+
+#[test]
+fn test_parsed_module_asm() {
+    let context = Context::new();
+    let module = Module::parse_ir(context.as_ref(), "module asm \"nop\"\nmodule asm \"\\09ret\"\n").unwrap();
+    module.append_inline_asm("int3");
+    assert!(module.print_module_to_string().contains("module asm \"nop\"\nmodule asm \"\\09ret\"\nmodule asm \"int3\"\n"));
+}
\ No newline at end of file
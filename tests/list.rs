@@ -58,7 +58,7 @@ fn test_list() {
 
         // // ptr.value = i
         builder.build_store(builder.build_struct_gep(builder.build_load(ptr), 0), i32_ptr);
-        builder.build_store(context.SInt32(i as u64), builder.build_load(i32_ptr));
+        builder.build_store(context.SInt32(i as i64), builder.build_load(i32_ptr));
 
         // // ptr.next = next pointer
         let tmp = builder.build_struct_gep(builder.build_load(ptr), 1);
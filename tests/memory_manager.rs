@@ -0,0 +1,159 @@
+#![cfg(unix)]
+
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate libc;
+
+use llvm_sys_wrapper::*;
+use std::cell::RefCell;
+use std::env;
+use std::mem;
+use std::process::Command;
+use std::ptr;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct Usage {
+    code_bytes: usize,
+    data_bytes: usize,
+    section_names: Vec<String>,
+    finalized: bool,
+}
+
+// mmap based memory manager tracking the memory used by the JIT
+struct TrackingMemoryManager {
+    usage: Rc<RefCell<Usage>>,
+    code_sections: Vec<(*mut u8, usize)>,
+    // every mapping with its page rounded length, released in destroy()
+    mappings: Vec<(*mut u8, usize)>,
+    limit: usize,
+}
+
+impl TrackingMemoryManager {
+    fn allocate(&mut self, size: usize) -> *mut u8 {
+        let usage = self.usage.borrow();
+        if usage.code_bytes + usage.data_bytes + size > self.limit {
+            return ptr::null_mut();
+        }
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let length = ((size + page_size - 1) / page_size * page_size).max(page_size);
+        let memory = unsafe {
+            libc::mmap(ptr::null_mut(), length, libc::PROT_READ | libc::PROT_WRITE,
+                       libc::MAP_PRIVATE | libc::MAP_ANONYMOUS, -1, 0)
+        };
+        assert!(memory != libc::MAP_FAILED);
+        self.mappings.push((memory as *mut u8, length));
+        memory as *mut u8
+    }
+}
+
+impl MemoryManager for TrackingMemoryManager {
+    fn allocate_code_section(&mut self, size: usize, _alignment: u32, _section_id: u32, section_name: &str) -> *mut u8 {
+        let memory = self.allocate(size);
+        self.code_sections.push((memory, size));
+        let mut usage = self.usage.borrow_mut();
+        usage.code_bytes += size;
+        usage.section_names.push(section_name.to_string());
+        memory
+    }
+
+    fn allocate_data_section(&mut self, size: usize, _alignment: u32, _section_id: u32, section_name: &str, _is_read_only: bool) -> *mut u8 {
+        let memory = self.allocate(size);
+        let mut usage = self.usage.borrow_mut();
+        usage.data_bytes += size;
+        usage.section_names.push(section_name.to_string());
+        memory
+    }
+
+    fn finalize_memory(&mut self) -> Result<(), String> {
+        for &(memory, size) in self.code_sections.iter() {
+            let result = unsafe { libc::mprotect(memory as *mut libc::c_void, size.max(1), libc::PROT_READ | libc::PROT_EXEC) };
+            if result != 0 {
+                return Err("mprotect failed".to_string());
+            }
+        }
+        self.usage.borrow_mut().finalized = true;
+        Ok(())
+    }
+
+    fn destroy(&mut self) {
+        for &(memory, length) in self.mappings.iter() {
+            unsafe { libc::munmap(memory as *mut libc::c_void, length) };
+        }
+        self.mappings.clear();
+        self.code_sections.clear();
+    }
+}
+
+// Int32 triple(Int32 x) { ret x * 3 }
+fn build_module(context: &Context) -> Module {
+    let builder = context.create_builder();
+    let module = context.create_module("memory_manager");
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let function = module.add_function("triple", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret(builder.build_mul(function.get_param(0), context.SInt32(3)));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+    module
+}
+
+fn create_engine(module: &Module, usage: &Rc<RefCell<Usage>>, limit: usize) -> Engine {
+    let manager = TrackingMemoryManager {
+        usage: usage.clone(),
+        code_sections: Vec::new(),
+        mappings: Vec::new(),
+        limit: limit,
+    };
+    let options = McJitOptions::new()
+        .opt_level(2)
+        .no_frame_pointer_elim(true)
+        .enable_fast_isel(false)
+        .memory_manager(manager);
+    module.create_jit_engine_with_options(options).unwrap()
+}
+
+#[test]
+fn test_memory_manager() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    let context = Context::global_context();
+    let module = build_module(&context);
+    let usage = Rc::new(RefCell::new(Usage::default()));
+    let engine = create_engine(&module, &usage, 1 << 20);
+
+    let address = engine.get_function_address("triple").unwrap();
+    let triple: extern "C" fn(i32) -> i32 = unsafe { mem::transmute(address as usize) };
+    assert_eq!(triple(14), 42);
+
+    let usage = usage.borrow();
+    assert!(usage.finalized);
+    assert!(usage.code_bytes > 0);
+    assert!(usage.section_names.iter().any(|name| name == ".text"));
+}
+
+// a failed allocation ends the process, so it is run in a child process
+#[test]
+fn test_memory_limit_ends_process() {
+    if env::var("LLVM_SYS_WRAPPER_MEMORY_LIMIT_CHILD").is_ok() {
+        LLVM::initialize();
+        let context = Context::global_context();
+        let module = build_module(&context);
+        let usage = Rc::new(RefCell::new(Usage::default()));
+        let engine = create_engine(&module, &usage, 0);
+        engine.get_function_address("triple");
+        return;
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .arg("--exact").arg("test_memory_limit_ends_process").arg("--nocapture")
+        .env("LLVM_SYS_WRAPPER_MEMORY_LIMIT_CHILD", "1")
+        .output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("LLVM ERROR"));
+}
\ No newline at end of file
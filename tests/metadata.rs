@@ -0,0 +1,87 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_metadata() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("metadata");
+
+    // Int32 check(Int32*, Int32**)
+    let int32_ptr_type = context.PointerType(context.Int32Type());
+    let fun_type = fn_type!(context.Int32Type(), int32_ptr_type, context.PointerType(int32_ptr_type));
+    let function = module.add_function("check", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    let then_block = function.append_basic_block("then");
+    let else_block = function.append_basic_block("else");
+    builder.position_at_end(entry_block);
+
+    // !range !{i32 0, i32 10}
+    let value = builder.build_load(function.get_param(0));
+    let mut range_values = [context.SInt32(0), context.SInt32(10)];
+    let range = context.MDNode(&mut range_values);
+    let range_kind = context.metadata_kind_id("range");
+    Instruction::from_ptr(value).set_metadata(range_kind, range);
+
+    // !nonnull !{}
+    let pointer = builder.build_load(function.get_param(1));
+    let nonnull_kind = context.metadata_kind_id("nonnull");
+    Instruction::from_ptr(pointer).set_metadata(nonnull_kind, context.MDNode(&mut []));
+
+    // !prof !{!"branch_weights", i32 90, i32 10}
+    let cond = builder.build_icmp_eq(value, context.SInt32(0));
+    let br = builder.build_cond_br(cond, then_block, else_block);
+    let mut weights = [context.MDString("branch_weights").as_ref(), context.UInt32(90), context.UInt32(10)];
+    let prof_kind = context.metadata_kind_id("prof");
+    Instruction::from_ptr(br).set_metadata(prof_kind, context.MDNode(&mut weights));
+
+    // custom metadata kind
+    builder.position_at_end(then_block);
+    let ret = builder.build_ret(context.SInt32(1));
+    let custom_kind = context.metadata_kind_id("my.custom");
+    let mut custom_values = [context.MDString("custom").as_ref()];
+    Instruction::from_ptr(ret).set_metadata(custom_kind, context.MDNode(&mut custom_values));
+
+    builder.position_at_end(else_block);
+    builder.build_ret(builder.build_load(pointer));
+
+    // module level named metadata
+    let mut version = [context.MDString("my compiler 1.0").as_ref()];
+    module.add_named_metadata_operand("compiler.version", context.MDNode(&mut version));
+
+    // read back
+    let range_md = Instruction::from_ptr(value).get_metadata(range_kind).unwrap();
+    assert!(range_md.is_node());
+    assert_eq!(range_md.operands_count(), 2);
+
+    assert!(Instruction::from_ptr(pointer).has_metadata());
+    assert!(Instruction::from_ptr(value).get_metadata(nonnull_kind).is_none());
+
+    let prof_md = Instruction::from_ptr(br).get_metadata(prof_kind).unwrap();
+    let prof_operands = prof_md.get_operands();
+    assert_eq!(prof_operands.len(), 3);
+    assert_eq!(Metadata::from_ptr(prof_operands[0]).get_string(), Some("branch_weights".to_string()));
+
+    let custom_md = Instruction::from_ptr(ret).get_metadata(custom_kind).unwrap();
+    assert_eq!(Metadata::from_ptr(custom_md.get_operands()[0]).get_string(), Some("custom".to_string()));
+
+    assert_eq!(module.named_metadata_operands_count("compiler.version"), 1);
+    let named = module.get_named_metadata_operands("compiler.version");
+    assert_eq!(named.len(), 1);
+    assert_eq!(Metadata::from_ptr(named[0].get_operands()[0]).get_string(), Some("my compiler 1.0".to_string()));
+    assert_eq!(module.named_metadata_operands_count("no.such.metadata"), 0);
+
+    // verify & dump
+    match module.verify() {
+        Ok(_) => { /* module.dump() */ },
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate llvm_sys;
+
+use llvm_sys_wrapper::*;
+use llvm_sys::target_machine::{LLVMCodeGenOptLevel, LLVMCodeModel};
+use std::env;
+use std::fs;
+use std::mem;
+use std::process;
+
+// Int64 poly(Int64 x) { ret x * x + 7 }
+fn build_module(context: &Context) -> Module {
+    let builder = context.create_builder();
+    let module = context.create_module("object_cache");
+    let fun_type = fn_type!(context.Int64Type(), context.Int64Type());
+    let function = module.add_function("poly", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let x = function.get_param(0);
+    builder.build_ret(builder.build_add(builder.build_mul(x, x), context.SInt64(7)));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+    module
+}
+
+fn run_poly(jit: &OrcJit, x: i64) -> i64 {
+    let poly: extern "C" fn(i64) -> i64 = unsafe { mem::transmute(jit.get_symbol_address("poly").unwrap() as usize) };
+    poly(x)
+}
+
+#[test]
+fn test_object_cache() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    let context = Context::global_context();
+    let dir = env::temp_dir().join(format!("llvm_sys_wrapper_object_cache_{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let cache = ObjectCache::new(&dir).unwrap();
+
+    // miss: compiled and stored
+    let module = build_module(&context);
+    let first_jit = OrcJit::new().unwrap();
+    let key = cache.key(&module, first_jit.target_machine());
+    assert!(cache.get(&key).is_none());
+    let (_, hit) = first_jit.add_module_with_cache(module, &cache).unwrap();
+    assert!(!hit);
+    let stored = cache.get(&key).unwrap();
+    assert!(!stored.is_empty());
+
+    // hit: an identical module maps to the same object
+    let module = build_module(&context);
+    let second_jit = OrcJit::new().unwrap();
+    assert_eq!(cache.key(&module, second_jit.target_machine()), key);
+    let (_, hit) = second_jit.add_module_with_cache(module, &cache).unwrap();
+    assert!(hit);
+    assert_eq!(cache.get(&key).unwrap(), stored);
+
+    // the module level entry point shares the cache and keeps the module with the caller
+    let module = build_module(&context);
+    let (third_jit, hit) = module.create_cached_jit(&cache).unwrap();
+    assert!(hit);
+
+    for x in [-3i64, 0, 5, 1 << 20].iter() {
+        assert_eq!(run_poly(&first_jit, *x), x * x + 7);
+        assert_eq!(run_poly(&second_jit, *x), run_poly(&first_jit, *x));
+        assert_eq!(run_poly(&third_jit, *x), run_poly(&first_jit, *x));
+    }
+
+    // only finished objects are left in the directory
+    for entry in fs::read_dir(&dir).unwrap() {
+        assert!(entry.unwrap().path().extension().unwrap() == "o");
+    }
+
+    // different target machine settings give a different key
+    let module = build_module(&context);
+    let target_machine = first_jit.target_machine();
+    assert_eq!(target_machine.get_code_model(), LLVMCodeModel::LLVMCodeModelJITDefault);
+    let other_opt_level = TargetMachine::new(&target_machine.get_triple(), &target_machine.get_cpu(), &target_machine.get_feature_string(),
+                                             LLVMCodeGenOptLevel::LLVMCodeGenLevelNone,
+                                             target_machine.get_reloc_mode(), target_machine.get_code_model()).unwrap();
+    assert!(cache.key(&module, &other_opt_level) != key);
+    let other_cpu = TargetMachine::new(&target_machine.get_triple(), "generic", "",
+                                       target_machine.get_opt_level(), target_machine.get_reloc_mode(),
+                                       target_machine.get_code_model()).unwrap();
+    assert!(cache.key(&module, &other_cpu) != key);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::mem;
+
+#[test]
+fn test_orc_jit() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+    let builder = context.create_builder();
+    let jit = OrcJit::new().unwrap();
+
+    // first module: Int32 add(Int32, Int32)
+    let module = context.create_module("orc_add");
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type(), context.Int32Type());
+    let add_func = module.add_function("add", fun_type);
+    let entry_block = add_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret(builder.build_add(add_func.get_param(0), add_func.get_param(1)));
+    assert!(module.verify().is_ok());
+    let add_handle = jit.add_module(module).unwrap();
+
+    // second module: Int32 add_abs(Int32 x) = add(abs(x), 1), calling into the first module and libc
+    let module = context.create_module("orc_add_abs");
+    let add_func = module.add_function("add", fun_type);
+    let abs_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let abs_func = module.add_function("abs", abs_type);
+    let add_abs_func = module.add_function("add_abs", abs_type);
+    let entry_block = add_abs_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    let mut args = [add_abs_func.get_param(0)];
+    let abs_value = builder.build_call(abs_func.as_ref(), &mut args);
+    let mut args = [abs_value, context.SInt32(1)];
+    builder.build_ret(builder.build_call(add_func.as_ref(), &mut args));
+    assert!(module.verify().is_ok());
+    let add_abs_handle = jit.add_module_eagerly(module).unwrap();
+
+    let add_address = jit.get_symbol_address("add").unwrap();
+    let add: extern "C" fn(i32, i32) -> i32 = unsafe { mem::transmute(add_address as usize) };
+    assert_eq!(add(2, 3), 5);
+
+    let add_abs_address = jit.get_symbol_address("add_abs").unwrap();
+    let add_abs: extern "C" fn(i32) -> i32 = unsafe { mem::transmute(add_abs_address as usize) };
+    assert_eq!(add_abs(-41), 42);
+
+    // modules can be removed again
+    jit.remove_module(add_abs_handle).unwrap();
+    assert!(jit.get_symbol_address("add_abs").is_none());
+    jit.remove_module(add_handle).unwrap();
+    assert!(jit.get_symbol_address("add").is_none());
+}
\ No newline at end of file
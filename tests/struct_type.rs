@@ -0,0 +1,51 @@
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_named_struct() {
+    // create context
+    let context = Context::new();
+    let module = context.create_module("struct_type");
+
+    // opaque until the body is set, as for the recursive Pair in tests/list.rs
+    let pair_type = context.StructTypeNamed("Pair");
+    assert_eq!(pair_type.name(), Some("Pair".to_string()));
+    assert!(pair_type.is_opaque());
+    assert_eq!(pair_type.field_count(), 0);
+
+    let pair_pointer_type = context.PointerType(pair_type.as_ref());
+    let mut field_types = [context.Int32Type(), pair_pointer_type];
+    pair_type.set_body(&mut field_types, false);
+    assert!(!pair_type.is_opaque());
+    assert!(!pair_type.is_packed());
+    assert_eq!(pair_type.field_count(), 2);
+    assert_eq!(pair_type.field_types(), vec![context.Int32Type(), pair_pointer_type]);
+
+    // look up by name after creation
+    assert_eq!(module.get_struct_by_name("Pair"), Some(pair_type));
+    assert!(module.get_struct_by_name("Missing").is_none());
+    let found = module.get_struct_by_name("Pair").unwrap();
+    let next_type = TypeInfo::from_ptr(found.field_types()[1]);
+    assert_eq!(next_type.kind(), TypeKind::Pointer);
+    assert_eq!(Struct::from_ptr(next_type.element_type()), pair_type);
+
+    // literal and packed structs have no name
+    let mut packed_fields = [context.Int8Type(), context.Int32Type()];
+    let packed_type = context.StructType(&mut packed_fields, true);
+    assert!(packed_type.is_packed());
+    assert_eq!(packed_type.name(), None);
+
+    // named constant struct
+    let mut values = [context.SInt32(1), context.PointerNull(pair_pointer_type)];
+    let pair = pair_type.new_const_named_struct(&mut values);
+    assert_eq!(Value::from_ptr(pair).get_type(), pair_type.as_ref());
+    let global = module.add_global_with_initializer("last_pair", pair, true);
+    assert!(module.print_module_to_string().contains("@last_pair = constant %Pair { i32 1, %Pair* null }"));
+    assert_eq!(Value::from_ptr(global).get_name(), "last_pair");
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+}
\ No newline at end of file
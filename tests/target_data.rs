@@ -0,0 +1,83 @@
+extern crate llvm_sys_wrapper;
+extern crate llvm_sys;
+
+use llvm_sys_wrapper::*;
+use llvm_sys::target_machine::{LLVMCodeGenOptLevel, LLVMRelocMode, LLVMCodeModel};
+
+const X86_64_LAYOUT: &str = "e-m:e-i64:64-f80:128-n8:16:32:64-S128";
+
+#[test]
+fn test_target_data() {
+    // create context
+    let context = Context::global_context();
+    let target_data = TargetData::new(X86_64_LAYOUT).unwrap();
+
+    // struct Record { Int8, Int32, Int64, Int8 }
+    let mut fields = [context.Int8Type(), context.Int32Type(), context.Int64Type(), context.Int8Type()];
+    let record = context.StructType(&mut fields, false);
+
+    assert_eq!(target_data.size_of_type(record.as_ref()), 24);
+    assert_eq!(target_data.abi_alignment(record.as_ref()), 8);
+    assert_eq!(target_data.offset_of_element(record.as_ref(), 0), 0);
+    assert_eq!(target_data.offset_of_element(record.as_ref(), 1), 4);
+    assert_eq!(target_data.offset_of_element(record.as_ref(), 2), 8);
+    assert_eq!(target_data.offset_of_element(record.as_ref(), 3), 16);
+    assert_eq!(target_data.element_at_offset(record.as_ref(), 5), 1);
+    assert_eq!(target_data.element_at_offset(record.as_ref(), 16), 3);
+
+    // packed struct has no padding
+    let packed = context.StructType(&mut fields, true);
+    assert_eq!(target_data.size_of_type(packed.as_ref()), 14);
+    assert_eq!(target_data.offset_of_element(packed.as_ref(), 2), 5);
+
+    // scalar types
+    assert_eq!(target_data.size_of_type_in_bits(context.Int1Type()), 1);
+    assert_eq!(target_data.store_size_of_type(context.Int1Type()), 1);
+    assert_eq!(target_data.size_of_type(context.X86FP80Type()), 16);
+    assert_eq!(target_data.preferred_alignment(context.Int64Type()), 8);
+    assert_eq!(target_data.pointer_size(), 8);
+    assert_eq!(target_data.int_ptr_type(context.as_ref()), context.Int64Type());
+    assert_eq!(target_data.get_data_layout(), X86_64_LAYOUT);
+
+    // module data layout
+    let module = context.create_module("target_data");
+    module.set_data_layout(X86_64_LAYOUT).unwrap();
+    assert_eq!(module.data_layout(), X86_64_LAYOUT);
+    assert_eq!(module.get_target_data().size_of_type(record.as_ref()), 24);
+}
+
+#[test]
+fn test_invalid_data_layout() {
+    // layouts LLVM itself produces
+    assert!(TargetData::new("").is_ok());
+    assert!(TargetData::new("E-m:m-p:32:32-i8:8:32-i16:16:32-i64:64-n32-S64").is_ok());
+    assert!(TargetData::new("e-m:w-p:32:32-i64:64-f80:32-n8:16:32-a:0:32-S32").is_ok());
+    assert!(TargetData::new("e-p:64:64-p1:64:64-p2:64:64-p3:32:32-p4:64:64-p5:32:32-A5-ni:7").is_ok());
+
+    // these would make LLVM abort the process
+    for layout in ["x", "e-", "e--m:e", "-e", "m:q", "m", "p:64", "p:63:64", "p:64:48", "i64", "i64:12",
+                   "i64:64:32", "a32:0:64", "n8:0", "n8:", "S", "S12", "ni", "ni:0", "i64:abc", ":64"].iter() {
+        assert!(TargetData::new(layout).is_err(), "{}", layout);
+    }
+
+    let module = Context::new().create_module("invalid_data_layout");
+    assert!(module.set_data_layout("e-q").is_err());
+    assert_eq!(module.data_layout(), "");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_target_data_from_target_machine() {
+    LLVM::initialize();
+
+    let target_machine = TargetMachine::new("x86_64-unknown-linux-gnu", "", "",
+                                            LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+                                            LLVMRelocMode::LLVMRelocDefault,
+                                            LLVMCodeModel::LLVMCodeModelDefault).unwrap();
+    assert_eq!(target_machine.get_triple(), "x86_64-unknown-linux-gnu");
+
+    let target_data = target_machine.create_target_data();
+    let context = Context::global_context();
+    assert_eq!(target_data.size_of_type(context.Int64Type()), 8);
+    assert_eq!(target_data.pointer_size(), 8);
+}
\ No newline at end of file
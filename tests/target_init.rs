@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+extern crate llvm_sys;
+
+use llvm_sys_wrapper::*;
+use llvm_sys::target_machine::{LLVMCodeGenOptLevel, LLVMRelocMode, LLVMCodeModel, LLVMCodeGenFileType};
+
+fn build_answer_module(context: &Context, name: &str, triple: &str, target_machine: &TargetMachine) -> Module {
+    let builder = context.create_builder();
+    let module = context.create_module(name);
+    module.set_target_triple(triple);
+    module.set_data_layout(&target_machine.create_target_data().get_data_layout()).unwrap();
+
+    // Int32 answer()
+    let fun_type = fn_type!(context.Int32Type());
+    let function = module.add_function("answer", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret(context.SInt32(42));
+
+    module
+}
+
+#[test]
+fn test_initialize_native_target() {
+    // repeated initialization is harmless
+    assert!(LLVM::initialize_native_target().is_ok());
+    assert!(LLVM::initialize_native_target().is_ok());
+    LLVM::initialize();
+}
+
+#[test]
+#[cfg(feature = "target-aarch64")]
+fn test_emit_aarch64_object() {
+    LLVM::initialize_target(TargetArch::AArch64).unwrap();
+    LLVM::initialize_target(TargetArch::AArch64).unwrap();
+
+    let triple = "aarch64-unknown-linux-gnu";
+    let target_machine = TargetMachine::new(triple, "generic", "",
+                                            LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+                                            LLVMRelocMode::LLVMRelocPIC,
+                                            LLVMCodeModel::LLVMCodeModelDefault).unwrap();
+    let context = Context::new();
+    let module = build_answer_module(&context, "aarch64", triple, &target_machine);
+
+    let object = target_machine.emit_to_memory(&module, LLVMCodeGenFileType::LLVMObjectFile).unwrap();
+    assert_eq!(&object[0..4], b"\x7fELF");
+    // e_machine == EM_AARCH64
+    assert_eq!(object[18] as u16 | (object[19] as u16) << 8, 183);
+
+    let assembly = target_machine.emit_to_memory(&module, LLVMCodeGenFileType::LLVMAssemblyFile).unwrap();
+    assert!(String::from_utf8_lossy(&assembly).contains("answer:"));
+}
+
+#[test]
+fn test_initialize_all_targets() {
+    LLVM::initialize_all_targets();
+    LLVM::initialize_all_targets();
+
+    let triple = "armv7-none-eabi";
+    let target = Target::from_triple(triple).unwrap();
+    assert_eq!(target.get_name(), "arm");
+    let target_machine = target.create_target_machine(triple, "cortex-m4", "",
+                                                      LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
+                                                      LLVMRelocMode::LLVMRelocStatic,
+                                                      LLVMCodeModel::LLVMCodeModelDefault);
+    let context = Context::new();
+    let module = build_answer_module(&context, "arm", triple, &target_machine);
+    let object = target_machine.emit_to_memory(&module, LLVMCodeGenFileType::LLVMObjectFile).unwrap();
+    // e_machine == EM_ARM
+    assert_eq!(object[18] as u16 | (object[19] as u16) << 8, 40);
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_target_triple() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // new module has no target
+    let module = context.create_module("target_triple");
+    assert_eq!(module.target_triple(), "");
+    assert_eq!(module.data_layout(), "");
+
+    // explicit triple
+    module.set_target_triple("aarch64-unknown-linux-gnu");
+    assert_eq!(module.target_triple(), "aarch64-unknown-linux-gnu");
+
+    // host
+    let triple = Target::default_triple();
+    assert!(!triple.is_empty());
+    let target = Target::host().unwrap();
+    assert!(!target.get_name().is_empty());
+
+    let host_module = context.create_module("host");
+    host_module.configure_for_host().unwrap();
+    assert_eq!(host_module.target_triple(), triple);
+    assert!(!host_module.data_layout().is_empty());
+    assert_eq!(host_module.get_target_data().pointer_size() as usize, std::mem::size_of::<usize>());
+}
\ No newline at end of file
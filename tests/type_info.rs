@@ -0,0 +1,66 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_type_info() {
+    // create context
+    let context = Context::global_context();
+    let module = context.create_module("type_info");
+
+    // integer
+    let int32 = TypeInfo::from_ptr(context.Int32Type());
+    assert_eq!(int32.kind(), TypeKind::Integer);
+    assert_eq!(int32.int_width(), 32);
+    assert!(int32.is_sized());
+    assert_eq!(format!("{}", int32), "i32");
+
+    // floating point
+    assert_eq!(TypeInfo::from_ptr(context.DoubleType()).kind(), TypeKind::Double);
+    assert_eq!(TypeInfo::from_ptr(context.VoidType()).kind(), TypeKind::Void);
+
+    // pointer
+    let char_ptr = TypeInfo::from_ptr(context.CharPointerType());
+    assert_eq!(char_ptr.kind(), TypeKind::Pointer);
+    assert_eq!(char_ptr.element_type(), context.Int8Type());
+    assert_eq!(char_ptr.pointer_address_space(), 0);
+    assert_eq!(char_ptr.to_string(), "i8*");
+
+    // array
+    let array = TypeInfo::from_ptr(context.ArrayType(context.Int64Type(), 8));
+    assert_eq!(array.kind(), TypeKind::Array);
+    assert_eq!(array.array_length(), 8);
+    assert_eq!(array.element_type(), context.Int64Type());
+    assert_eq!(array.to_string(), "[8 x i64]");
+
+    // struct
+    let pair_type = context.StructTypeNamed("TypeInfoPair");
+    let mut field_types = [context.Int32Type(), context.PointerType(pair_type.as_ref())];
+    pair_type.set_body(&mut field_types, false);
+    let pair = TypeInfo::from_ptr(pair_type.as_ref());
+    assert_eq!(pair.kind(), TypeKind::Struct);
+    assert_eq!(pair.struct_name(), Some("TypeInfoPair".to_string()));
+    assert_eq!(pair.struct_field_count(), 2);
+    assert_eq!(pair.struct_field_types(), field_types.to_vec());
+    assert_eq!(pair.to_string(), "%TypeInfoPair = type { i32, %TypeInfoPair* }");
+
+    // function with several parameters
+    let fun_type = fn_type!(context.Int32Type(), context.Int8Type(), context.Int64Type(), context.DoubleType());
+    let function = module.add_function("three_params", fun_type);
+    let fun_info = TypeInfo::from_ptr(fun_type);
+    assert_eq!(fun_info.kind(), TypeKind::Function);
+    assert_eq!(fun_info.return_type(), context.Int32Type());
+    assert_eq!(fun_info.param_count(), 3);
+    assert!(!fun_info.is_var_arg());
+    assert_eq!(function.get_param_types(), vec![context.Int8Type(), context.Int64Type(), context.DoubleType()]);
+    assert_eq!(fun_info.to_string(), "i32 (i8, i64, double)");
+
+    // function looked up by name
+    let named_function = module.named_function("three_params");
+    assert_eq!(named_function.get_return_type(), context.Int32Type());
+    assert_eq!(named_function.get_param_types().len(), 3);
+
+    let printf_type = fn_type!(context.Int32Type(), context.CharPointerType() ,,,);
+    assert!(TypeInfo::from_ptr(printf_type).is_var_arg());
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_value() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("value");
+
+    // Int32 calc(Int32 x)
+    let fun_type = fn_type!(context.Int32Type(), context.Int32Type());
+    let function = module.add_function("calc", fun_type);
+    let entry_block = function.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let x = Value::from_ptr(function.get_param(0));
+    assert_eq!(x.get_name(), "");
+    x.set_name("x");
+    assert_eq!(x.get_name(), "x");
+
+    let sum = builder.build_add_with_name(x.as_ref(), context.SInt32(1), "sum");
+    let product = builder.build_mul_with_name(sum, sum, "product");
+    builder.build_ret(product);
+
+    // name, type and printing
+    let sum_value = Value::from_ptr(sum);
+    assert_eq!(sum_value.get_name(), "sum");
+    assert_eq!(sum_value.get_type(), context.Int32Type());
+    assert_eq!(sum_value.get_type_info().kind(), TypeKind::Integer);
+    assert_eq!(format!("{}", sum_value).trim(), "%sum = add i32 %x, 1");
+    assert!(sum_value.is_instruction());
+    assert!(!sum_value.is_constant());
+
+    // constants
+    let one = Value::from_ptr(context.SInt32(1));
+    assert!(one.is_constant());
+    assert!(!one.is_undef());
+    assert!(!one.is_null());
+    assert_eq!(one.to_string(), "i32 1");
+    assert!(Value::from_ptr(context.Null(context.Int32Type())).is_null());
+    assert!(Value::from_ptr(unsafe { LLVMGetUndef(context.Int32Type()) }).is_undef());
+
+    // uses and users
+    assert_eq!(sum_value.uses_count(), 2);
+    let users = sum_value.get_users();
+    assert_eq!(users.len(), 2);
+    assert!(users.iter().all(|user| *user == product));
+    assert_eq!(x.get_users(), vec![sum]);
+
+    // replace all uses of %sum with %x
+    sum_value.replace_all_uses_with(x.as_ref());
+    assert_eq!(sum_value.uses_count(), 0);
+    assert_eq!(x.uses_count(), 3);
+    assert_eq!(Value::from_ptr(product).to_string().trim(), "%product = mul i32 %x, %x");
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+#![cfg(all(target_arch = "x86_64", not(windows)))]
+
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+use std::mem;
+
+#[test]
+fn test_varargs() {
+    // initialize LLVM
+    LLVM::initialize();
+
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("varargs");
+
+    // x86-64 System V va_list
+    let mut va_list_fields = [context.Int32Type(), context.Int32Type(), context.Int8PointerType(), context.Int8PointerType()];
+    let va_list_type = context.ArrayType(context.StructType(&mut va_list_fields, false).as_ref(), 1);
+
+    // Int32 sum(Int32 count, ...)
+    let sum_type = fn_type!(context.Int32Type(), context.Int32Type() ,,,);
+    let sum_func = module.add_function("sum", sum_type);
+    assert!(sum_func.is_var_arg());
+    let entry_block = sum_func.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+
+    let va_list = builder.build_alloca(va_list_type);
+    let va_copy = builder.build_alloca(va_list_type);
+    let acc = builder.build_alloca(context.Int32Type());
+    builder.build_store(context.SInt32(0), acc);
+    builder.build_va_start(va_list);
+    builder.build_va_copy(va_copy, va_list);
+    builder.build_for(context.SInt32(0), sum_func.get_param(0), context.SInt32(1), |b, _| {
+        let value = b.build_va_arg(va_list, context.Int32Type());
+        b.build_store(b.build_add(b.build_load(acc), value), acc);
+    });
+    // the copy still starts at the first variadic argument
+    let first = builder.build_va_arg(va_copy, context.Int32Type());
+    builder.build_va_end(va_copy);
+    builder.build_va_end(va_list);
+    builder.build_ret(builder.build_sub(builder.build_load(acc), first));
+
+    // Int32 call_sum() { ret sum(3, 10, 20, 12) }
+    let call_func = module.add_function("call_sum", fn_type!(context.Int32Type()));
+    builder.position_at_end(call_func.append_basic_block("entry"));
+    let mut args = [context.SInt32(3), context.SInt32(10), context.SInt32(20), context.SInt32(12)];
+    builder.build_ret(builder.build_call(sum_func.as_ref(), &mut args));
+
+    match module.verify() {
+        Ok(_) => {},
+        Err(msg) => panic!("Error: {}", msg)
+    }
+
+    let engine = module.create_jit_engine().unwrap();
+    let call_sum: extern "C" fn() -> i32 = unsafe { mem::transmute(engine.get_function_address("call_sum").unwrap() as usize) };
+    assert_eq!(call_sum(), 32);
+
+    // also callable as a C variadic function from Rust
+    let sum: unsafe extern "C" fn(i32, ...) -> i32 = unsafe { mem::transmute(engine.get_function_address("sum").unwrap() as usize) };
+    assert_eq!(unsafe { sum(4, 1i32, 2i32, 3i32, 4i32) }, 9);
+}
\ No newline at end of file
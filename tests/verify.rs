@@ -0,0 +1,53 @@
+#[macro_use]
+extern crate llvm_sys_wrapper;
+
+use llvm_sys_wrapper::*;
+
+#[test]
+fn test_function_verify() {
+    // create context
+    let context = Context::global_context();
+
+    // setup our builder and module
+    let builder = context.create_builder();
+    let module = context.create_module("verify");
+
+    // Int32 good() { ret i32 7 }
+    let fun_type = fn_type!(context.Int32Type());
+    let good = module.add_function("good", fun_type);
+    let entry_block = good.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret(context.SInt32(7));
+
+    // Int32 bad() { ret void }
+    let bad = module.add_function("bad", fun_type);
+    let entry_block = bad.append_basic_block("entry");
+    builder.position_at_end(entry_block);
+    builder.build_ret_void();
+
+    assert!(good.verify().is_ok());
+
+    let err = bad.verify().unwrap_err();
+    assert_eq!(err.diagnostics().len(), 1);
+    let diagnostic = &err.diagnostics()[0];
+    assert_eq!(diagnostic.message(), "Function return type does not match operand type of return inst!");
+    assert_eq!(diagnostic.function(), Some("bad"));
+    assert_eq!(diagnostic.instruction(), Some("ret void"));
+
+    // module verification reports the same diagnostic
+    match module.verify() {
+        Err(Error::Verify(err)) => {
+            assert!(err.diagnostics().iter().any(|d| d.function() == Some("bad")));
+            assert!(err.diagnostics().iter().all(|d| d.function() != Some("good")));
+        },
+        _ => panic!("broken module passed verification")
+    }
+
+    // block iteration used to locate instructions
+    let blocks = bad.get_basic_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].get_name(), "entry");
+    assert_eq!(bad.get_entry_block(), Some(blocks[0]));
+    assert!(blocks[0].get_terminator().is_some());
+    assert_eq!(module.get_functions().len(), 2);
+}
\ No newline at end of file